@@ -0,0 +1,142 @@
+//! Server-signed attestations of verification results, as compact Ed25519 JWS.
+
+use bdk_wallet::bitcoin::{
+    base64::{engine::general_purpose::URL_SAFE_NO_PAD, Engine as _},
+    hashes::{sha256, Hash},
+    hex::FromHex,
+};
+use ed25519_dalek::{Signature, Signer, SigningKey, Verifier, VerifyingKey};
+use serde_json::json;
+
+pub struct Attestor {
+    signing_key: SigningKey,
+}
+
+impl Attestor {
+    /// Create an attestor from a hex encoded 32 byte Ed25519 seed.
+    pub fn from_hex(seed: &str) -> Result<Self, String> {
+        let seed = <[u8; 32]>::from_hex(seed.trim())
+            .map_err(|e| format!("Invalid attestation key: {:?}", e))?;
+        Ok(Attestor {
+            signing_key: SigningKey::from_bytes(&seed),
+        })
+    }
+
+    fn verifying_key(&self) -> VerifyingKey {
+        self.signing_key.verifying_key()
+    }
+
+    /// A short identifier of the public key, derived from its hash.
+    pub fn key_id(&self) -> String {
+        sha256::Hash::hash(self.verifying_key().as_bytes()).to_string()[..16].to_string()
+    }
+
+    /// The public key as JSON Web Key.
+    pub fn jwk(&self) -> serde_json::Value {
+        json!({
+            "kty": "OKP",
+            "crv": "Ed25519",
+            "alg": "EdDSA",
+            "use": "sig",
+            "kid": self.key_id(),
+            "x": URL_SAFE_NO_PAD.encode(self.verifying_key().as_bytes()),
+        })
+    }
+
+    /// Sign the payload as compact JWS.
+    pub fn sign(&self, payload: &serde_json::Value) -> String {
+        let header = json!({ "alg": "EdDSA", "kid": self.key_id() });
+        let signing_input = format!(
+            "{}.{}",
+            URL_SAFE_NO_PAD.encode(header.to_string()),
+            URL_SAFE_NO_PAD.encode(payload.to_string())
+        );
+        let signature = self.signing_key.sign(signing_input.as_bytes());
+        format!(
+            "{}.{}",
+            signing_input,
+            URL_SAFE_NO_PAD.encode(signature.to_bytes())
+        )
+    }
+
+    /// Check that the compact JWS was signed by this server and return its payload.
+    pub fn verify(&self, jws: &str) -> Result<serde_json::Value, String> {
+        let mut parts = jws.trim().split('.');
+        let (Some(header), Some(payload), Some(signature), None) =
+            (parts.next(), parts.next(), parts.next(), parts.next())
+        else {
+            return Err("Attestation is not a compact JWS".to_string());
+        };
+
+        let header: serde_json::Value = URL_SAFE_NO_PAD
+            .decode(header)
+            .ok()
+            .and_then(|header| serde_json::from_slice(&header).ok())
+            .ok_or_else(|| "Invalid attestation header".to_string())?;
+        if header["alg"] != "EdDSA" {
+            return Err(format!("Unsupported algorithm {}", header["alg"]));
+        }
+        if header["kid"] != self.key_id() {
+            return Err("Attestation was signed with a different key".to_string());
+        }
+
+        let signature = URL_SAFE_NO_PAD
+            .decode(signature)
+            .ok()
+            .and_then(|signature| Signature::from_slice(&signature).ok())
+            .ok_or_else(|| "Invalid attestation signature encoding".to_string())?;
+        let signing_input = &jws.trim()[..jws.trim().rfind('.').unwrap_or_default()];
+        self.verifying_key()
+            .verify(signing_input.as_bytes(), &signature)
+            .map_err(|_| "Invalid attestation signature".to_string())?;
+
+        URL_SAFE_NO_PAD
+            .decode(payload)
+            .ok()
+            .and_then(|payload| serde_json::from_slice(&payload).ok())
+            .ok_or_else(|| "Invalid attestation payload".to_string())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SEED: &str = "9d61b19deffd5a60ba844af492ec2cc44449c5697b326919703bac031cae7f60";
+
+    #[test]
+    fn test_sign_and_verify() {
+        let attestor = Attestor::from_hex(SEED).unwrap();
+        let payload = json!({ "spendable": 10 });
+        let jws = attestor.sign(&payload);
+        assert_eq!(attestor.verify(&jws), Ok(payload));
+
+        let (signing_input, _) = jws.rsplit_once('.').unwrap();
+        let (header, _) = signing_input.split_once('.').unwrap();
+        let forged = format!(
+            "{}.{}.{}",
+            header,
+            URL_SAFE_NO_PAD.encode(json!({ "spendable": 11 }).to_string()),
+            jws.rsplit_once('.').unwrap().1
+        );
+        assert_eq!(
+            attestor.verify(&forged),
+            Err("Invalid attestation signature".to_string())
+        );
+
+        let other = Attestor::from_hex(&SEED.replace('9', "8")).unwrap();
+        assert!(other.verify(&jws).is_err());
+        assert!(attestor.verify("not a jws").is_err());
+    }
+
+    #[test]
+    fn test_jwk() {
+        let attestor = Attestor::from_hex(SEED).unwrap();
+        // public key of the RFC 8032 test vector 1
+        assert_eq!(
+            attestor.jwk()["x"],
+            "11qYAYKxCrfVS_7TyWQHOg7hcvPapiMlrwIaaPcHURo"
+        );
+        assert!(Attestor::from_hex("00").is_err());
+    }
+}
@@ -0,0 +1,290 @@
+//! Optional API key authentication with roles, for private deployments.
+//!
+//! Keys are configured as SHA256 hashes, in the `[auth]` section of the configuration or in a
+//! separate TOML file, so the configuration does not reveal them:
+//!
+//! ```toml
+//! [[keys]]
+//! name = "dashboards"
+//! role = "verify"
+//! sha256 = "<hex encoded SHA256 of the key>"
+//! ```
+//!
+//! Clients pass the key in the `X-Api-Key` header or as `Authorization: Bearer <key>`.
+
+use actix_web::{
+    body::MessageBody,
+    dev::{ServiceRequest, ServiceResponse},
+    http::{header, Method},
+    middleware::Next,
+    web, Error, HttpResponse,
+};
+use bdk_wallet::bitcoin::hashes::{sha256, Hash};
+use serde::{Deserialize, Serialize};
+use serde_json::json;
+use std::{collections::HashMap, fs, sync::Arc};
+
+/// What a key may do, each role includes the ones before it.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum Role {
+    /// Verify proofs and read results.
+    Verify,
+    /// Manage monitors and webhooks.
+    MonitorManager,
+    /// Server management like flushing the cache.
+    Admin,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ApiKey {
+    pub name: String,
+    pub role: Role,
+    /// The hex encoded SHA256 of the key.
+    #[serde(skip_serializing)]
+    pub sha256: String,
+}
+
+#[derive(Debug, Default, Deserialize)]
+struct KeysFile {
+    #[serde(default)]
+    keys: Vec<ApiKey>,
+}
+
+pub struct ApiKeys {
+    by_hash: HashMap<String, ApiKey>,
+}
+
+impl ApiKeys {
+    pub fn new(keys: Vec<ApiKey>) -> Self {
+        ApiKeys {
+            by_hash: keys
+                .into_iter()
+                .map(|key| (key.sha256.to_lowercase(), key))
+                .collect(),
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.by_hash.len()
+    }
+
+    /// The configured keys, without their hashes.
+    pub fn list(&self) -> Vec<&ApiKey> {
+        let mut keys = self.by_hash.values().collect::<Vec<_>>();
+        keys.sort_by(|a, b| a.name.cmp(&b.name));
+        keys
+    }
+
+    fn find(&self, key: &str) -> Option<&ApiKey> {
+        self.by_hash
+            .get(&sha256::Hash::hash(key.as_bytes()).to_string())
+    }
+
+    /// The configured key a request comes with, if any.
+    pub fn authenticate(&self, headers: &header::HeaderMap) -> Option<&ApiKey> {
+        request_key(headers).and_then(|key| self.find(key))
+    }
+}
+
+/// The keys of a keys file.
+pub fn load_keys(path: &str) -> Result<Vec<ApiKey>, String> {
+    let content = fs::read_to_string(path)
+        .map_err(|e| format!("Failed to read API keys from {}: {:?}", path, e))?;
+    let file: KeysFile =
+        toml::from_str(&content).map_err(|e| format!("Invalid API keys file {}: {}", path, e))?;
+    Ok(file.keys)
+}
+
+/// The role needed for a request, `None` for the public pages.
+fn required_role(method: &Method, path: &str) -> Option<Role> {
+    if method == Method::GET && ["/", "/inclusion", "/healthz", "/readyz"].contains(&path) {
+        return None;
+    }
+    if path.starts_with("/admin") {
+        Some(Role::Admin)
+    } else if path.starts_with("/monitors") || path.starts_with("/webhooks") {
+        Some(Role::MonitorManager)
+    } else {
+        Some(Role::Verify)
+    }
+}
+
+/// The API key of a request, from the `X-Api-Key` or the `Authorization` header.
+fn request_key(headers: &header::HeaderMap) -> Option<&str> {
+    headers
+        .get("X-Api-Key")
+        .and_then(|key| key.to_str().ok())
+        .or_else(|| {
+            headers
+                .get(header::AUTHORIZATION)
+                .and_then(|authorization| authorization.to_str().ok())
+                .and_then(|authorization| authorization.strip_prefix("Bearer "))
+        })
+}
+
+/// Check the API key and role of a request, returning the answer to send instead if it is not
+/// allowed.
+fn authorize(
+    keys: Option<&ApiKeys>,
+    method: &Method,
+    path: &str,
+    headers: &header::HeaderMap,
+) -> Option<HttpResponse> {
+    let required = required_role(method, path)?;
+    let Some(keys) = keys else {
+        // without keys the server is public, except for the admin endpoints
+        return match required {
+            Role::Admin => Some(
+                HttpResponse::Forbidden()
+                    .content_type("text/json")
+                    .body(json!({ "error": "Admin endpoints require API keys" }).to_string()),
+            ),
+            _ => None,
+        };
+    };
+    match keys.authenticate(headers) {
+        None => Some(
+            HttpResponse::Unauthorized()
+                .content_type("text/json")
+                .insert_header((header::WWW_AUTHENTICATE, "Bearer"))
+                .body(json!({ "error": "Missing or unknown API key" }).to_string()),
+        ),
+        Some(key) if key.role < required => Some(
+            HttpResponse::Forbidden().content_type("text/json").body(
+                json!({ "error": format!("API key {} is not allowed to do this", key.name) })
+                    .to_string(),
+            ),
+        ),
+        Some(_) => None,
+    }
+}
+
+/// Middleware rejecting requests without a key of the required role.
+pub async fn middleware(
+    req: ServiceRequest,
+    next: Next<impl MessageBody + 'static>,
+) -> Result<ServiceResponse<impl MessageBody>, Error> {
+    let keys = req.app_data::<web::Data<ApiKeys>>().cloned();
+    // the percent-decoded path the routes match, not the raw one of the URI
+    match authorize(
+        keys.as_deref().map(Arc::as_ref),
+        req.method(),
+        req.match_info().as_str(),
+        req.headers(),
+    ) {
+        None => next
+            .call(req)
+            .await
+            .map(ServiceResponse::map_into_left_body),
+        Some(response) => Ok(req.into_response(response).map_into_right_body()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use actix_web::{
+        http::{header::HeaderValue, StatusCode},
+        middleware::from_fn,
+        test::{call_service, init_service, TestRequest},
+        App,
+    };
+
+    fn headers(name: &'static str, value: &str) -> header::HeaderMap {
+        let mut headers = header::HeaderMap::new();
+        headers.insert(
+            header::HeaderName::from_static(name),
+            HeaderValue::from_str(value).unwrap(),
+        );
+        headers
+    }
+
+    fn status(refusal: Option<HttpResponse>) -> StatusCode {
+        refusal.map_or(StatusCode::OK, |response| response.status())
+    }
+
+    #[test]
+    fn test_authorize() {
+        let file: KeysFile = toml::from_str(&format!(
+            "[[keys]]\nname = \"ops\"\nrole = \"monitor-manager\"\nsha256 = \"{}\"",
+            sha256::Hash::hash(b"secret")
+        ))
+        .unwrap();
+        let keys = ApiKeys::new(file.keys);
+        let post = Method::POST;
+        let none = header::HeaderMap::new();
+        let key = headers("x-api-key", "secret");
+        let bearer = headers("authorization", "Bearer secret");
+        let wrong = headers("x-api-key", "wrong");
+
+        assert_eq!(
+            status(authorize(Some(&keys), &Method::GET, "/", &none)),
+            StatusCode::OK
+        );
+        assert_eq!(
+            status(authorize(Some(&keys), &Method::GET, "/readyz", &none)),
+            StatusCode::OK
+        );
+        assert_eq!(
+            status(authorize(Some(&keys), &post, "/proof", &none)),
+            StatusCode::UNAUTHORIZED
+        );
+        assert_eq!(
+            status(authorize(Some(&keys), &post, "/proof", &wrong)),
+            StatusCode::UNAUTHORIZED
+        );
+        assert_eq!(
+            status(authorize(Some(&keys), &post, "/proof", &key)),
+            StatusCode::OK
+        );
+        assert_eq!(
+            status(authorize(Some(&keys), &post, "/monitors", &bearer)),
+            StatusCode::OK
+        );
+        assert_eq!(
+            status(authorize(Some(&keys), &post, "/admin/cache/flush", &key)),
+            StatusCode::FORBIDDEN
+        );
+
+        // without keys only the admin endpoints are closed
+        assert_eq!(
+            status(authorize(None, &post, "/proof", &none)),
+            StatusCode::OK
+        );
+        assert_eq!(
+            status(authorize(None, &post, "/admin/cache/flush", &key)),
+            StatusCode::FORBIDDEN
+        );
+    }
+
+    #[actix_web::test]
+    async fn test_encoded_path() {
+        let app = App::new()
+            .wrap(from_fn(middleware))
+            .route("/admin/cache/flush", web::post().to(HttpResponse::Ok));
+        let app = init_service(app).await;
+
+        // routed like /admin/cache/flush, so still closed without keys
+        let req = TestRequest::post().uri("/%61dmin/cache/flush").to_request();
+        let resp = call_service(&app, req).await;
+        assert_eq!(resp.status(), StatusCode::FORBIDDEN);
+
+        let keys = ApiKeys::new(vec![ApiKey {
+            name: "dashboards".to_string(),
+            role: Role::Verify,
+            sha256: sha256::Hash::hash(b"secret").to_string(),
+        }]);
+        let app = App::new()
+            .app_data(web::Data::new(keys))
+            .wrap(from_fn(middleware))
+            .route("/monitors", web::post().to(HttpResponse::Ok));
+        let app = init_service(app).await;
+        let req = TestRequest::post()
+            .uri("/%6Donitors")
+            .insert_header(("X-Api-Key", "secret"))
+            .to_request();
+        let resp = call_service(&app, req).await;
+        assert_eq!(resp.status(), StatusCode::FORBIDDEN);
+    }
+}
@@ -0,0 +1,205 @@
+//! The chain data a verification needs, and the Electrum servers providing it.
+//!
+//! A backend serves one network and is shared by the verifications, embedders can implement
+//! [`Backend`] over their own node or indexer.
+
+use crate::{limits::Budget, metrics::metrics};
+use bdk_electrum::{
+    electrum_client::{self, ConfigBuilder, ElectrumApi},
+    BdkElectrumClient,
+};
+use bdk_wallet::{
+    bitcoin::{BlockHash, OutPoint, Script, Transaction, Txid},
+    KeychainKind, Wallet,
+};
+use std::{
+    collections::BTreeMap,
+    sync::{Arc, Mutex},
+};
+
+/// Scripts requested from the server at once when scanning a wallet.
+const BATCH_SIZE: usize = 10;
+
+/// The current chain tip.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Tip {
+    pub height: usize,
+    pub hash: BlockHash,
+    /// The header time, in seconds since the epoch.
+    pub time: u32,
+}
+
+/// An unspent output of a script.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Unspent {
+    pub outpoint: OutPoint,
+    /// The height of the block confirming it, 0 when unconfirmed.
+    pub height: usize,
+}
+
+pub trait Backend: Send + Sync {
+    /// Identifies the backend in the cache, the history and the metrics, like its URL.
+    fn name(&self) -> &str;
+
+    fn tip(&self) -> Result<Tip, String>;
+
+    fn block_hash(&self, height: usize) -> Result<BlockHash, String>;
+
+    /// The unspent outputs of a script, confirmed or not.
+    fn unspent(&self, script: &Script) -> Result<Vec<Unspent>, String>;
+
+    fn transaction(&self, txid: &Txid) -> Result<Transaction, String>;
+
+    /// Scan the scripts of a wallet up to `stop_gap` unused ones in a row, and apply what was
+    /// found. Every scanned script and found transaction costs a call of the budget.
+    fn full_scan(
+        &self,
+        wallet: &mut Wallet,
+        stop_gap: usize,
+        budget: &mut Budget,
+    ) -> Result<(), String> {
+        let _ = (wallet, stop_gap, budget);
+        Err(format!("The backend {} cannot scan wallets", self.name()))
+    }
+}
+
+/// An Electrum server, connected on first use and again after a failed call. Wallet scans use a
+/// connection of their own, closed when they end.
+pub struct ElectrumBackend {
+    server: String,
+    /// Seconds to wait for the server.
+    timeout: u8,
+    client: Mutex<Option<Arc<electrum_client::Client>>>,
+}
+
+impl ElectrumBackend {
+    pub fn new(server: &str, timeout: u8) -> Self {
+        ElectrumBackend {
+            server: server.to_string(),
+            timeout,
+            client: Mutex::new(None),
+        }
+    }
+
+    fn connect(&self) -> Result<electrum_client::Client, String> {
+        metrics()
+            .backend_call(&self.server, "connect", || {
+                let config = ConfigBuilder::new().timeout(Some(self.timeout)).build();
+                electrum_client::Client::from_config(&self.server, config)
+            })
+            .map_err(|e| format!("Failed to create Electrum client: {:?}", e))
+    }
+
+    fn client(&self) -> Result<Arc<electrum_client::Client>, String> {
+        let mut client = self.client.lock().unwrap();
+        if let Some(client) = client.as_ref() {
+            return Ok(client.clone());
+        }
+        let connected = Arc::new(self.connect()?);
+        *client = Some(connected.clone());
+        Ok(connected)
+    }
+
+    /// Make a counted call, dropping the connection if it fails.
+    fn call<T>(
+        &self,
+        call: &'static str,
+        failure: &str,
+        f: impl FnOnce(&electrum_client::Client) -> Result<T, electrum_client::Error>,
+    ) -> Result<T, String> {
+        let client = self.client()?;
+        metrics()
+            .backend_call(&self.server, call, || f(&client))
+            .map_err(|e| {
+                *self.client.lock().unwrap() = None;
+                format!("{}: {:?}", failure, e)
+            })
+    }
+}
+
+impl Backend for ElectrumBackend {
+    fn name(&self) -> &str {
+        &self.server
+    }
+
+    fn tip(&self) -> Result<Tip, String> {
+        self.call(
+            "block_headers_subscribe",
+            "Failed to get block height",
+            |client| client.block_headers_subscribe(),
+        )
+        .map(|tip| Tip {
+            height: tip.height,
+            hash: tip.header.block_hash(),
+            time: tip.header.time,
+        })
+    }
+
+    fn block_hash(&self, height: usize) -> Result<BlockHash, String> {
+        self.call("block_header", "Failed to get block header", |client| {
+            client.block_header(height)
+        })
+        .map(|header| header.block_hash())
+    }
+
+    fn unspent(&self, script: &Script) -> Result<Vec<Unspent>, String> {
+        let unspents = self.call("script_list_unspent", "Failed to list utxos", |client| {
+            client.script_list_unspent(script)
+        })?;
+        Ok(unspents
+            .iter()
+            .map(|utxo| Unspent {
+                outpoint: OutPoint {
+                    txid: utxo.tx_hash,
+                    vout: utxo.tx_pos as u32,
+                },
+                height: utxo.height,
+            })
+            .collect())
+    }
+
+    fn transaction(&self, txid: &Txid) -> Result<Transaction, String> {
+        self.call("transaction_get", "Failed to get transaction", |client| {
+            client.transaction_get(txid)
+        })
+    }
+
+    fn full_scan(
+        &self,
+        wallet: &mut Wallet,
+        stop_gap: usize,
+        budget: &mut Budget,
+    ) -> Result<(), String> {
+        // every script costs a call, the keychains share what is left of the budget and one
+        // using up its share may have more scripts in use
+        let keychains = wallet.all_unbounded_spk_iters();
+        let max_scripts = budget.remaining() / keychains.len().max(1);
+        let mut request = wallet.start_full_scan();
+        for (keychain, scripts) in keychains {
+            request = request.spks_for_keychain(keychain, scripts.take(max_scripts));
+        }
+        let scanned = Arc::new(Mutex::new(BTreeMap::<KeychainKind, usize>::new()));
+        let counter = scanned.clone();
+        let request = request
+            .inspect(move |keychain, _, _| {
+                *counter.lock().unwrap().entry(keychain).or_default() += 1
+            })
+            .build();
+        // on a connection of its own, a BdkElectrumClient caches every transaction and header it
+        // fetched for as long as it lives
+        let client = BdkElectrumClient::new(self.connect()?);
+        let update = metrics()
+            .backend_call(&self.server, "full_scan", || {
+                client.full_scan(request, stop_gap, BATCH_SIZE, false)
+            })
+            .map_err(|e| format!("Failed to scan wallet: {:?}", e))?;
+        let scanned = scanned.lock().unwrap();
+        if scanned.values().any(|scripts| *scripts >= max_scripts) {
+            return Err(budget.exhausted());
+        }
+        budget.spend_many(scanned.values().sum::<usize>() + update.tx_update.txs.len())?;
+        wallet
+            .apply_update(update)
+            .map_err(|e| format!("Failed to apply wallet update: {:?}", e))
+    }
+}
@@ -0,0 +1,167 @@
+//! In-memory cache of verification results and utxo lookups.
+//!
+//! Entries are keyed on the anchor block hash, and all entries of a backend are dropped as soon
+//! as its chain tip changes, be it through a new block or a reorg.
+
+use crate::{metrics::metrics, Reserves};
+use bdk_wallet::bitcoin::{
+    hashes::{sha256, Hash, HashEngine},
+    BlockHash, OutPoint, ScriptBuf, TxOut,
+};
+use std::{
+    collections::{BTreeSet, HashMap},
+    sync::Mutex,
+};
+
+/// Entries per kind and backend, the cache starts over when there are more.
+const MAX_ENTRIES: usize = 10_000;
+
+#[derive(Default)]
+pub struct Cache {
+    backends: Mutex<HashMap<String, BackendCache>>,
+}
+
+#[derive(Default)]
+struct BackendCache {
+    tip: Option<BlockHash>,
+    results: HashMap<sha256::Hash, Reserves>,
+    utxos: HashMap<(ScriptBuf, BlockHash), Vec<(OutPoint, TxOut)>>,
+}
+
+impl Cache {
+    /// Record the current tip of a backend, dropping its entries if the tip changed.
+    pub fn update_tip(&self, server: &str, tip: BlockHash) {
+        let mut backends = self.backends.lock().unwrap();
+        let backend = backends.entry(server.to_string()).or_default();
+        if backend.tip != Some(tip) {
+            *backend = BackendCache {
+                tip: Some(tip),
+                ..Default::default()
+            };
+        }
+    }
+
+    pub fn result(&self, server: &str, key: &sha256::Hash) -> Option<Reserves> {
+        let backends = self.backends.lock().unwrap();
+        let result = backends
+            .get(server)
+            .and_then(|backend| backend.results.get(key).cloned());
+        metrics().cache_lookup("result", result.is_some());
+        result
+    }
+
+    pub fn insert_result(&self, server: &str, key: sha256::Hash, reserves: &Reserves) {
+        let mut backends = self.backends.lock().unwrap();
+        let results = &mut backends.entry(server.to_string()).or_default().results;
+        if results.len() >= MAX_ENTRIES {
+            results.clear();
+        }
+        results.insert(key, reserves.clone());
+    }
+
+    /// Drop all entries, returning how many there were.
+    pub fn clear(&self) -> usize {
+        let mut backends = self.backends.lock().unwrap();
+        let entries = backends
+            .values()
+            .map(|backend| backend.results.len() + backend.utxos.len())
+            .sum();
+        backends.clear();
+        entries
+    }
+
+    /// The confirmed utxos of a script up to the anchor block.
+    pub fn utxos(
+        &self,
+        server: &str,
+        script: &ScriptBuf,
+        anchor: &BlockHash,
+    ) -> Option<Vec<(OutPoint, TxOut)>> {
+        let backends = self.backends.lock().unwrap();
+        let utxos = backends
+            .get(server)
+            .and_then(|backend| backend.utxos.get(&(script.clone(), *anchor)).cloned());
+        metrics().cache_lookup("utxos", utxos.is_some());
+        utxos
+    }
+
+    pub fn insert_utxos(
+        &self,
+        server: &str,
+        script: ScriptBuf,
+        anchor: BlockHash,
+        utxos: &[(OutPoint, TxOut)],
+    ) {
+        let mut backends = self.backends.lock().unwrap();
+        let cached = &mut backends.entry(server.to_string()).or_default().utxos;
+        if cached.len() >= MAX_ENTRIES {
+            cached.clear();
+        }
+        cached.insert((script, anchor), utxos.to_vec());
+    }
+}
+
+/// The key of a verification result: the proof, the message, the set of addresses and the
+/// anchor block.
+pub fn result_key(
+    psbt: &str,
+    message: &str,
+    addresses: &[String],
+    anchor: &BlockHash,
+) -> sha256::Hash {
+    let mut engine = sha256::Hash::engine();
+    engine.input(sha256::Hash::hash(psbt.as_bytes()).as_byte_array());
+    engine.input(sha256::Hash::hash(message.as_bytes()).as_byte_array());
+    for address in addresses.iter().collect::<BTreeSet<_>>() {
+        engine.input(sha256::Hash::hash(address.as_bytes()).as_byte_array());
+    }
+    engine.input(anchor.as_byte_array());
+    sha256::Hash::from_engine(engine)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn reserves(spendable: u64) -> Reserves {
+        Reserves {
+            spendable,
+            block_height: 100,
+            block_hash: BlockHash::all_zeros(),
+            addresses: Vec::new(),
+            utxos: Vec::new(),
+            cached: false,
+        }
+    }
+
+    #[test]
+    fn test_cache() {
+        let cache = Cache::default();
+        let tip = BlockHash::from_byte_array([1; 32]);
+        let anchor = BlockHash::all_zeros();
+        let addresses = vec!["a".to_string(), "b".to_string()];
+        let key = result_key("psbt", "message", &addresses, &anchor);
+
+        cache.update_tip("server", tip);
+        cache.insert_result("server", key, &reserves(10));
+        cache.insert_utxos("server", ScriptBuf::new(), anchor, &[]);
+        assert_eq!(cache.result("server", &key).unwrap().spendable, 10);
+        assert!(cache.result("other", &key).is_none());
+        assert!(cache.utxos("server", &ScriptBuf::new(), &anchor).is_some());
+
+        // the order of the addresses does not matter, the anchor block does
+        let reordered = vec!["b".to_string(), "a".to_string()];
+        assert_eq!(result_key("psbt", "message", &reordered, &anchor), key);
+        assert_ne!(result_key("psbt", "message", &addresses, &tip), key);
+
+        cache.update_tip("server", tip);
+        assert!(cache.result("server", &key).is_some());
+        cache.update_tip("server", BlockHash::from_byte_array([2; 32]));
+        assert!(cache.result("server", &key).is_none());
+        assert!(cache.utxos("server", &ScriptBuf::new(), &anchor).is_none());
+
+        cache.insert_result("server", key, &reserves(10));
+        assert_eq!(cache.clear(), 1);
+        assert!(cache.result("server", &key).is_none());
+    }
+}
@@ -0,0 +1,143 @@
+//! Coalescing of identical concurrent verifications.
+//!
+//! The first request for a key starts the verification on a blocking thread, the requests for
+//! the same key arriving before it finished wait for its result instead of starting their own.
+
+use actix_web::rt;
+use std::{
+    collections::HashMap,
+    sync::{Arc, Mutex},
+};
+use tokio::sync::watch;
+use tracing::Span;
+
+type Pending = watch::Receiver<Option<serde_json::Value>>;
+
+#[derive(Default)]
+pub struct InFlight {
+    requests: Arc<Mutex<HashMap<String, Pending>>>,
+}
+
+/// Removes the key of a verification when it finishes, even by panicking, so the next request
+/// starts a new one.
+struct Finished {
+    requests: Arc<Mutex<HashMap<String, Pending>>>,
+    key: String,
+}
+
+impl Drop for Finished {
+    fn drop(&mut self) {
+        // a second panic while unwinding would abort the process
+        let mut requests = self.requests.lock().unwrap_or_else(|e| e.into_inner());
+        requests.remove(&self.key);
+    }
+}
+
+impl InFlight {
+    /// Run the verification unless one for the same key is in flight already, returning its
+    /// result and whether it was shared with an earlier request.
+    pub async fn run<F>(&self, key: String, verify: F) -> Result<(serde_json::Value, bool), String>
+    where
+        F: FnOnce() -> serde_json::Value + Send + 'static,
+    {
+        let (mut pending, shared) = {
+            let mut requests = self.requests.lock().unwrap();
+            match requests.get(&key) {
+                Some(pending) => (pending.clone(), true),
+                None => {
+                    let (sender, pending) = watch::channel(None);
+                    requests.insert(key.clone(), pending.clone());
+                    // finish on the blocking thread, so waiting requests get the result even
+                    // if the client which started the verification went away
+                    let finished = Finished {
+                        requests: self.requests.clone(),
+                        key,
+                    };
+                    let span = Span::current();
+                    rt::task::spawn_blocking(move || {
+                        // dropped before sending, and on a panic, which drops the sender and
+                        // lets the waiting requests fail
+                        let finished = finished;
+                        let result = span.in_scope(verify);
+                        drop(finished);
+                        let _ = sender.send(Some(result));
+                    });
+                    (pending, false)
+                }
+            }
+        };
+
+        let result = pending
+            .wait_for(Option::is_some)
+            .await
+            .map_err(|_| "Verification aborted".to_string())?
+            .clone()
+            .unwrap_or_default();
+        Ok((result, shared))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+    use std::{
+        sync::atomic::{AtomicUsize, Ordering},
+        thread,
+        time::Duration,
+    };
+
+    #[actix_web::test]
+    async fn test_coalescing() {
+        let in_flight = Arc::new(InFlight::default());
+        let runs = Arc::new(AtomicUsize::new(0));
+        let start = |key: &str| {
+            let in_flight = in_flight.clone();
+            let runs = runs.clone();
+            let key = key.to_string();
+            rt::spawn(async move {
+                in_flight
+                    .run(key.clone(), move || {
+                        runs.fetch_add(1, Ordering::SeqCst);
+                        thread::sleep(Duration::from_millis(200));
+                        json!({ "key": key })
+                    })
+                    .await
+            })
+        };
+
+        let first = start("proof");
+        let second = start("proof");
+        let other = start("other");
+        let (first, second, other) = (
+            first.await.unwrap().unwrap(),
+            second.await.unwrap().unwrap(),
+            other.await.unwrap().unwrap(),
+        );
+        assert_eq!(runs.load(Ordering::SeqCst), 2);
+        assert_eq!(first.0, json!({ "key": "proof" }));
+        assert_eq!(first.0, second.0);
+        assert!(!first.1 && second.1);
+        assert_eq!(other, (json!({ "key": "other" }), false));
+
+        // once finished, the same request is verified again
+        let again = start("proof").await.unwrap().unwrap();
+        assert!(!again.1);
+        assert_eq!(runs.load(Ordering::SeqCst), 3);
+    }
+
+    #[actix_web::test]
+    async fn test_panicking_verification() {
+        let in_flight = InFlight::default();
+        let result = in_flight
+            .run("proof".to_string(), || panic!("verification panicked"))
+            .await;
+        assert_eq!(result, Err("Verification aborted".to_string()));
+
+        // the next request does not wait for the dead one
+        let result = in_flight
+            .run("proof".to_string(), || json!({ "spendable": 10 }))
+            .await;
+        assert_eq!(result, Ok((json!({ "spendable": 10 }), false)));
+    }
+}
@@ -0,0 +1,232 @@
+//! The `verify` command, checking a proof from the terminal like `/proof` does.
+//!
+//! It exits with 0 when the proof verifies and covers the claimed amount, 1 when the proof does
+//! not verify or falls short of the claimed amount, and 2 when it could not be checked.
+
+use crate::{
+    config::Config, metrics, ClaimedAmount, ProofOfReserves, Report, ReservesVerifier, Verdict,
+};
+use bdk_wallet::{
+    bitcoin::{
+        base64::{engine::general_purpose::STANDARD, Engine},
+        Amount, Network,
+    },
+    miniscript::{descriptor::DescriptorPublicKey, Descriptor},
+};
+use clap::{Args, Subcommand};
+use serde_json::json;
+use std::{fs, process::ExitCode, str::FromStr};
+
+#[derive(Debug, Subcommand)]
+pub enum Command {
+    /// Verify a proof of reserves against the configured backends and exit.
+    Verify(VerifyArgs),
+}
+
+#[derive(Debug, Args)]
+pub struct VerifyArgs {
+    /// File with the proof PSBT, base64 encoded or binary.
+    #[arg(long, value_name = "FILE")]
+    pub psbt: String,
+    /// The message the proof commits to.
+    #[arg(long)]
+    pub message: String,
+    /// Address holding reserves, repeat for more.
+    #[arg(
+        long = "address",
+        value_name = "ADDRESS",
+        required_unless_present = "descriptors"
+    )]
+    pub addresses: Vec<String>,
+    /// Watch-only descriptor of addresses holding reserves, repeat for more.
+    #[arg(long = "descriptor", value_name = "DESCRIPTOR")]
+    pub descriptors: Vec<String>,
+    /// Addresses derived from each descriptor with a wildcard.
+    #[arg(long, default_value_t = 20)]
+    pub derivation_count: u32,
+    /// Network of the addresses derived from the descriptors.
+    #[arg(long, default_value_t = Network::Bitcoin)]
+    pub network: Network,
+    /// Amount the reserves must cover, in sats, decimal BTC like 1.5, or denominated like "1500 mBTC".
+    #[arg(long)]
+    pub claimed_amount: Option<String>,
+    /// Print the report as JSON, like `/proof` answers.
+    #[arg(long)]
+    pub json: bool,
+}
+
+/// Run a command, returning the exit code.
+pub fn run(command: Command, config: &Config) -> ExitCode {
+    match command {
+        Command::Verify(args) => verify(&args, config),
+    }
+}
+
+fn verify(args: &VerifyArgs, config: &Config) -> ExitCode {
+    let result = ReservesVerifier::from_config(config, None)
+        .and_then(|verifier| proof(args).and_then(|proof| verifier.verify(&proof)));
+    if args.json {
+        match &result {
+            Ok(report) => println!("{}", json!(report)),
+            Err(e) => println!("{}", json!({ "error": e })),
+        }
+    } else {
+        println!("{}", render(&result));
+    }
+    ExitCode::from(exit_code(&result))
+}
+
+fn proof(args: &VerifyArgs) -> Result<ProofOfReserves, String> {
+    let psbt = fs::read(&args.psbt)
+        .map_err(|e| format!("Failed to read PSBT from {}: {:?}", args.psbt, e))?;
+    let mut addresses = args.addresses.clone();
+    for descriptor in &args.descriptors {
+        addresses.extend(descriptor_addresses(
+            descriptor,
+            args.derivation_count,
+            args.network,
+        )?);
+    }
+    Ok(ProofOfReserves {
+        addresses,
+        message: args.message.clone(),
+        proof_psbt: psbt_base64(&psbt),
+        claimed_amount: args.claimed_amount.clone().map(ClaimedAmount::Denominated),
+        confirmations: None,
+    })
+}
+
+/// A PSBT file as base64, whether it is binary or base64 already.
+fn psbt_base64(content: &[u8]) -> String {
+    if content.starts_with(b"psbt\xff") {
+        STANDARD.encode(content)
+    } else {
+        String::from_utf8_lossy(content).trim().to_string()
+    }
+}
+
+/// The addresses of a descriptor, the first ones if it has a wildcard.
+pub fn descriptor_addresses(
+    descriptor: &str,
+    count: u32,
+    network: Network,
+) -> Result<Vec<String>, String> {
+    let descriptor = Descriptor::<DescriptorPublicKey>::from_str(descriptor)
+        .map_err(|e| format!("Invalid descriptor: {:?}", e))?;
+    let count = if descriptor.has_wildcard() { count } else { 1 };
+    (0..count)
+        .map(|index| {
+            descriptor
+                .at_derivation_index(index)
+                .map_err(|e| format!("Invalid descriptor: {:?}", e))?
+                .address(network)
+                .map(|address| address.to_string())
+                .map_err(|e| format!("Invalid descriptor: {:?}", e))
+        })
+        .collect()
+}
+
+/// 0 when the proof verifies and covers the claimed amount, 1 when it does not, 2 when it could
+/// not be checked.
+fn exit_code(result: &Result<Report, String>) -> u8 {
+    match result {
+        Ok(report) => match &report.solvency {
+            Some(solvency) if solvency.verdict == Verdict::Fail => 1,
+            _ => 0,
+        },
+        Err(e) => match metrics::error_code(e).as_str() {
+            "no_address"
+            | "invalid_address"
+            | "invalid_claimed_amount"
+            | "too_many_backend_calls"
+            | "limit_exceeded"
+            | "invalid_block_height"
+            | "backend_error"
+            | "other" => 2,
+            _ => 1,
+        },
+    }
+}
+
+fn render(result: &Result<Report, String>) -> String {
+    let report = match result {
+        Ok(report) => report,
+        Err(e) => return format!("FAIL: {}", e),
+    };
+    let reserves = &report.reserves;
+    let mut lines = vec![
+        format!(
+            "Spendable: {} ({} sat) at block {} {}",
+            Amount::from_sat(reserves.spendable),
+            reserves.spendable,
+            reserves.block_height,
+            reserves.block_hash
+        ),
+        format!(
+            "Proven utxos: {} on {} addresses",
+            reserves.utxos.len(),
+            reserves.addresses.len()
+        ),
+    ];
+    lines.extend(
+        reserves
+            .utxos
+            .iter()
+            .map(|utxo| format!("  {} {} {} sat", utxo.outpoint, utxo.address, utxo.value)),
+    );
+    let verdict = match &report.solvency {
+        Some(solvency) => match (solvency.surplus, solvency.shortfall) {
+            (_, Some(shortfall)) => format!(
+                "FAIL: short of the claimed {} sat by {} sat",
+                solvency.claimed_amount, shortfall
+            ),
+            (surplus, None) => format!(
+                "PASS: covers the claimed {} sat with {} sat to spare",
+                solvency.claimed_amount,
+                surplus.unwrap_or_default()
+            ),
+        },
+        None => "PASS: the proof verifies".to_string(),
+    };
+    lines.push(verdict);
+    lines.join("\n")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{verifier::check_solvency, Reserves};
+    use bdk_wallet::bitcoin::{hashes::Hash, BlockHash};
+
+    #[test]
+    fn test_verify_command() {
+        assert_eq!(psbt_base64(b" cHNidP8B\n"), "cHNidP8B");
+        assert_eq!(psbt_base64(b"psbt\xff\x01"), "cHNidP8B");
+
+        let mut report = Report {
+            reserves: Reserves {
+                spendable: 150,
+                block_height: 800_000,
+                block_hash: BlockHash::all_zeros(),
+                addresses: vec!["address".to_string()],
+                utxos: Vec::new(),
+                cached: false,
+            },
+            solvency: None,
+        };
+        assert_eq!(exit_code(&Ok(report.clone())), 0);
+        report.solvency = Some(check_solvency(100, 150));
+        assert_eq!(exit_code(&Ok(report.clone())), 0);
+        assert!(render(&Ok(report.clone())).ends_with("with 50 sat to spare"));
+        report.solvency = Some(check_solvency(200, 150));
+        assert_eq!(exit_code(&Ok(report.clone())), 1);
+        assert!(render(&Ok(report)).starts_with("Spendable: 0.00000150 BTC (150 sat)"));
+
+        assert_eq!(exit_code(&Err("NonSpendableInput(1)".to_string())), 1);
+        assert_eq!(exit_code(&Err("Base64 decode error: x".to_string())), 1);
+        assert_eq!(
+            exit_code(&Err("Failed to list utxos: timeout".to_string())),
+            2
+        );
+    }
+}
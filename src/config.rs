@@ -0,0 +1,566 @@
+//! Server configuration from a TOML file, command line flags and environment variables.
+//!
+//! Settings are taken from the defaults, then the file given with `--config`, then the
+//! environment and finally the command line. See `config.example.toml` for all settings.
+
+use crate::{auth::ApiKey, command::Command, limits::Limits, logging, verifier::Confirmations};
+use bdk_wallet::bitcoin::Network;
+use clap::{Parser, ValueEnum};
+use serde::{Deserialize, Serialize};
+use std::{collections::BTreeMap, fs, str::FromStr};
+
+/// Command line flags, each of them can also be set through the environment.
+#[derive(Debug, Default, Parser)]
+#[command(version, about = "Web service verifying Bitcoin proofs of reserves")]
+pub struct Cli {
+    /// TOML configuration file.
+    #[arg(short, long, env = "CONFIG_FILE")]
+    pub config: Option<String>,
+    /// Validate and print the configuration, then exit.
+    #[arg(long)]
+    pub check_config: bool,
+    /// Run a command instead of the server, the flags above go before it.
+    #[command(subcommand)]
+    pub command: Option<Command>,
+    #[arg(long, env = "BIND_ADDRESS")]
+    pub bind_address: Option<String>,
+    /// Listen on all interfaces on this port, unless a bind address is given.
+    #[arg(long, env = "PORT")]
+    pub port: Option<u16>,
+    /// Electrum server of a network, like `testnet=ssl://electrum.blockstream.info:60002`.
+    #[arg(long = "backend", env = "BACKENDS", value_delimiter = ',', value_parser = parse_backend)]
+    pub backends: Vec<(Network, String)>,
+    /// Confirmations of the reported utxos when a request does not choose them.
+    #[arg(long, env = "CONFIRMATIONS")]
+    pub confirmations: Option<usize>,
+    #[arg(long, env = "MAX_CONFIRMATIONS")]
+    pub max_confirmations: Option<usize>,
+    /// Largest accepted JSON body in bytes.
+    #[arg(long, env = "PAYLOAD_LIMIT")]
+    pub payload_limit: Option<usize>,
+    #[arg(long, env = "MAX_ADDRESSES")]
+    pub max_addresses: Option<usize>,
+    #[arg(long, env = "MAX_INPUTS")]
+    pub max_inputs: Option<usize>,
+    #[arg(long, env = "MAX_UTXOS_PER_SCRIPT")]
+    pub max_utxos_per_script: Option<usize>,
+    #[arg(long, env = "MAX_BACKEND_CALLS")]
+    pub max_backend_calls: Option<usize>,
+    #[arg(long, env = "RATE_LIMIT_BURST")]
+    pub rate_limit_burst: Option<u32>,
+    /// Work units refilled per minute and client, 0 disables rate limiting.
+    #[arg(long, env = "RATE_LIMIT_PER_MINUTE")]
+    pub rate_limit_per_minute: Option<u32>,
+    #[arg(long, env = "JOB_WORKERS")]
+    pub job_workers: Option<usize>,
+    /// SQLite database of the verifications, only the latest are kept in memory if not set.
+    #[arg(long, env = "DATABASE_PATH")]
+    pub database_path: Option<String>,
+    /// Hex encoded Ed25519 seed signing the verification results.
+    #[arg(long, env = "ATTESTATION_KEY", hide_env_values = true)]
+    pub attestation_key: Option<String>,
+    #[arg(long, env = "ATTESTATION_KEY_FILE")]
+    pub attestation_key_file: Option<String>,
+    #[arg(long, env = "API_KEYS_FILE")]
+    pub api_keys_file: Option<String>,
+    #[arg(long, env = "TLS_CERT_FILE")]
+    pub tls_cert_file: Option<String>,
+    #[arg(long, env = "TLS_KEY_FILE")]
+    pub tls_key_file: Option<String>,
+    #[arg(long, env = "TLS_CLIENT_CA_FILE")]
+    pub tls_client_ca_file: Option<String>,
+    /// Seconds after which a backend tip is too old to be ready, 0 accepts any tip.
+    #[arg(long, env = "MAX_TIP_AGE")]
+    pub max_tip_age: Option<u64>,
+    /// One of off, error, warn, info, debug or trace.
+    #[arg(long, env = "LOG_LEVEL")]
+    pub log_level: Option<String>,
+    #[arg(long, env = "LOG_FORMAT")]
+    pub log_format: Option<LogFormat>,
+    /// How addresses and PSBTs appear in the logs.
+    #[arg(long, env = "LOG_REDACTION")]
+    pub log_redaction: Option<Redaction>,
+    /// Secret keying the redaction hashes, generated at startup when unset.
+    #[arg(long, env = "LOG_REDACTION_KEY", hide_env_values = true)]
+    pub log_redaction_key: Option<String>,
+    /// Features to turn off.
+    #[arg(long = "disable", env = "DISABLED_FEATURES", value_delimiter = ',')]
+    pub disabled: Vec<Feature>,
+}
+
+fn parse_backend(value: &str) -> Result<(Network, String), String> {
+    let (network, server) = value
+        .split_once('=')
+        .ok_or_else(|| format!("Expected <network>=<server>, got {}", value))?;
+    let network = Network::from_str(network).map_err(|e| format!("{:?}", e))?;
+    Ok((network, server.to_string()))
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct Config {
+    pub bind_address: String,
+    /// Largest accepted JSON body in bytes.
+    pub payload_limit: usize,
+    /// The Electrum server of each supported network.
+    pub backends: BTreeMap<Network, String>,
+    pub confirmations: Confirmations,
+    pub limits: Limits,
+    pub rate_limit: RateLimit,
+    pub jobs: JobsConfig,
+    pub storage: StorageConfig,
+    pub attestation: AttestationConfig,
+    pub auth: AuthConfig,
+    pub tls: TlsConfig,
+    pub health: HealthConfig,
+    pub logging: Logging,
+    pub features: Features,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct RateLimit {
+    pub burst: u32,
+    /// Work units refilled per minute, 0 disables rate limiting.
+    pub per_minute: u32,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct JobsConfig {
+    pub workers: usize,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct StorageConfig {
+    pub database_path: Option<String>,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct AttestationConfig {
+    pub key: Option<String>,
+    pub key_file: Option<String>,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct AuthConfig {
+    /// A file with more `[[keys]]`, see the `auth` module.
+    pub keys_file: Option<String>,
+    pub keys: Vec<ApiKey>,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct TlsConfig {
+    pub cert_file: Option<String>,
+    pub key_file: Option<String>,
+    pub client_ca_file: Option<String>,
+}
+
+/// The readiness checks of the backends.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct HealthConfig {
+    /// Seconds after which a tip is too old for the backend to be ready, 0 accepts any tip.
+    pub max_tip_age: u64,
+    /// Seconds to wait for a backend, in the checks and in the verifications.
+    pub timeout: u8,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct Logging {
+    pub level: String,
+    pub format: LogFormat,
+    /// How addresses and PSBTs appear in the logs.
+    pub redaction: Redaction,
+    /// Secret keying the HMAC of `hash` redaction, so that hashes can't be matched against
+    /// known addresses. Generated at startup when unset, hashes then only correlate within a run.
+    pub redaction_key: Option<String>,
+    /// Log every request.
+    pub access_log: bool,
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize, ValueEnum)]
+#[serde(rename_all = "lowercase")]
+pub enum LogFormat {
+    #[default]
+    Json,
+    Text,
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize, ValueEnum)]
+#[serde(rename_all = "lowercase")]
+pub enum Redaction {
+    /// Log them as they are.
+    Off,
+    /// Log a short keyed hash, to correlate the lines about the same wallet.
+    #[default]
+    Hash,
+    /// Leave them out.
+    Remove,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct Features {
+    pub metrics: bool,
+    pub jobs: bool,
+    pub streaming: bool,
+    /// Off by default, needs API keys as anyone could have the server watch wallets otherwise.
+    pub monitors: bool,
+    /// Off by default, needs API keys as anyone could have the server call out otherwise.
+    pub webhooks: bool,
+    pub cache: bool,
+    pub coalescing: bool,
+    /// Unsigned proofs of watch-only wallets at `/proof/unsigned`.
+    pub proof_construction: bool,
+}
+
+/// A feature which can be turned off.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum Feature {
+    Metrics,
+    Jobs,
+    Streaming,
+    Monitors,
+    Webhooks,
+    Cache,
+    Coalescing,
+    ProofConstruction,
+}
+
+impl Default for Config {
+    fn default() -> Self {
+        Config {
+            bind_address: "localhost:8087".to_string(),
+            payload_limit: 40960,
+            backends: BTreeMap::from([
+                (
+                    Network::Bitcoin,
+                    "ssl://electrum.blockstream.info:50002".to_string(),
+                ),
+                (
+                    Network::Testnet,
+                    "ssl://electrum.blockstream.info:60002".to_string(),
+                ),
+            ]),
+            confirmations: Confirmations::default(),
+            limits: Limits::default(),
+            rate_limit: RateLimit::default(),
+            jobs: JobsConfig::default(),
+            storage: StorageConfig::default(),
+            attestation: AttestationConfig::default(),
+            auth: AuthConfig::default(),
+            tls: TlsConfig::default(),
+            health: HealthConfig::default(),
+            logging: Logging::default(),
+            features: Features::default(),
+        }
+    }
+}
+
+impl Default for RateLimit {
+    fn default() -> Self {
+        RateLimit {
+            burst: 100,
+            per_minute: 60,
+        }
+    }
+}
+
+impl Default for JobsConfig {
+    fn default() -> Self {
+        JobsConfig { workers: 2 }
+    }
+}
+
+impl Default for HealthConfig {
+    fn default() -> Self {
+        HealthConfig {
+            max_tip_age: 3 * 60 * 60,
+            timeout: 5,
+        }
+    }
+}
+
+impl Default for Logging {
+    fn default() -> Self {
+        Logging {
+            level: "info".to_string(),
+            format: LogFormat::default(),
+            redaction: Redaction::default(),
+            redaction_key: None,
+            access_log: true,
+        }
+    }
+}
+
+impl Default for Features {
+    fn default() -> Self {
+        Features {
+            metrics: true,
+            jobs: true,
+            streaming: true,
+            monitors: false,
+            webhooks: false,
+            cache: true,
+            coalescing: true,
+            proof_construction: true,
+        }
+    }
+}
+
+impl Config {
+    /// The configuration file of the flags if there is one, with the flags applied on top.
+    pub fn load(cli: Cli) -> Result<Self, String> {
+        let config = match &cli.config {
+            Some(path) => {
+                let content = fs::read_to_string(path)
+                    .map_err(|e| format!("Failed to read config from {}: {:?}", path, e))?;
+                toml::from_str(&content)
+                    .map_err(|e| format!("Invalid config file {}: {}", path, e))?
+            }
+            None => Config::default(),
+        };
+        let config = config.with_overrides(cli);
+        config.validate()?;
+        Ok(config)
+    }
+
+    fn with_overrides(mut self, cli: Cli) -> Self {
+        fn set<T>(setting: &mut T, value: Option<T>) {
+            if let Some(value) = value {
+                *setting = value;
+            }
+        }
+        fn set_some<T>(setting: &mut Option<T>, value: Option<T>) {
+            if value.is_some() {
+                *setting = value;
+            }
+        }
+
+        set(
+            &mut self.bind_address,
+            cli.bind_address
+                .or_else(|| cli.port.map(|port| format!("0.0.0.0:{}", port))),
+        );
+        self.backends.extend(cli.backends);
+        set(&mut self.confirmations.default, cli.confirmations);
+        set(&mut self.confirmations.max, cli.max_confirmations);
+        set(&mut self.payload_limit, cli.payload_limit);
+        set(&mut self.limits.max_addresses, cli.max_addresses);
+        set(&mut self.limits.max_inputs, cli.max_inputs);
+        set(
+            &mut self.limits.max_utxos_per_script,
+            cli.max_utxos_per_script,
+        );
+        set(&mut self.limits.max_backend_calls, cli.max_backend_calls);
+        set(&mut self.rate_limit.burst, cli.rate_limit_burst);
+        set(&mut self.rate_limit.per_minute, cli.rate_limit_per_minute);
+        set(&mut self.jobs.workers, cli.job_workers);
+        set_some(&mut self.storage.database_path, cli.database_path);
+        if cli.attestation_key.is_some() || cli.attestation_key_file.is_some() {
+            self.attestation.key = cli.attestation_key;
+            self.attestation.key_file = cli.attestation_key_file;
+        }
+        set_some(&mut self.auth.keys_file, cli.api_keys_file);
+        set_some(&mut self.tls.cert_file, cli.tls_cert_file);
+        set_some(&mut self.tls.key_file, cli.tls_key_file);
+        set_some(&mut self.tls.client_ca_file, cli.tls_client_ca_file);
+        set(&mut self.health.max_tip_age, cli.max_tip_age);
+        set(&mut self.logging.level, cli.log_level);
+        set(&mut self.logging.format, cli.log_format);
+        set(&mut self.logging.redaction, cli.log_redaction);
+        set_some(&mut self.logging.redaction_key, cli.log_redaction_key);
+        for feature in cli.disabled {
+            *self.features.get_mut(feature) = false;
+        }
+        self
+    }
+
+    pub fn validate(&self) -> Result<(), String> {
+        if self.backends.is_empty() {
+            return Err("At least one backend is required".to_string());
+        }
+        if let Some((network, _)) = self.backends.iter().find(|(_, server)| server.is_empty()) {
+            return Err(format!("The {} backend has no server", network));
+        }
+        if self.confirmations.default > self.confirmations.max {
+            return Err(format!(
+                "The default confirmations {} are above the maximum {}",
+                self.confirmations.default, self.confirmations.max
+            ));
+        }
+        if self.payload_limit == 0 {
+            return Err("The payload limit must be positive".to_string());
+        }
+        let limits = &self.limits;
+        if [
+            limits.max_addresses,
+            limits.max_inputs,
+            limits.max_utxos_per_script,
+            limits.max_backend_calls,
+        ]
+        .contains(&0)
+        {
+            return Err(format!("Limits must be positive: {:?}", limits));
+        }
+        if self.rate_limit.per_minute > 0 && self.rate_limit.burst == 0 {
+            return Err("The rate limit burst must be positive".to_string());
+        }
+        if self.features.jobs && self.jobs.workers == 0 {
+            return Err("Jobs need at least one worker".to_string());
+        }
+        let features = &self.features;
+        if (features.monitors || features.webhooks)
+            && self.auth.keys.is_empty()
+            && self.auth.keys_file.is_none()
+        {
+            return Err("Monitors and webhooks require API keys".to_string());
+        }
+        if self.attestation.key.is_some() && self.attestation.key_file.is_some() {
+            return Err("Set either the attestation key or its file, not both".to_string());
+        }
+        match (&self.tls.cert_file, &self.tls.key_file) {
+            (Some(_), None) | (None, Some(_)) => {
+                return Err("TLS needs both a certificate and a key file".to_string())
+            }
+            (None, None) if self.tls.client_ca_file.is_some() => {
+                return Err("Client certificates require TLS".to_string())
+            }
+            _ => {}
+        }
+        if self.health.timeout == 0 {
+            return Err("The backend timeout must be positive".to_string());
+        }
+        logging::level(&self.logging)?;
+        Ok(())
+    }
+
+    /// The configuration as TOML, without secrets.
+    pub fn to_redacted_toml(&self) -> String {
+        let mut config = self.clone();
+        if config.attestation.key.is_some() {
+            config.attestation.key = Some("<redacted>".to_string());
+        }
+        if config.logging.redaction_key.is_some() {
+            config.logging.redaction_key = Some("<redacted>".to_string());
+        }
+        toml::to_string(&config).expect("serializable config")
+    }
+}
+
+impl Features {
+    fn get_mut(&mut self, feature: Feature) -> &mut bool {
+        match feature {
+            Feature::Metrics => &mut self.metrics,
+            Feature::Jobs => &mut self.jobs,
+            Feature::Streaming => &mut self.streaming,
+            Feature::Monitors => &mut self.monitors,
+            Feature::Webhooks => &mut self.webhooks,
+            Feature::Cache => &mut self.cache,
+            Feature::Coalescing => &mut self.coalescing,
+            Feature::ProofConstruction => &mut self.proof_construction,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ReservesVerifier;
+
+    fn cli(args: &[&str]) -> Cli {
+        Cli::try_parse_from([&["bdk-reserves-web"], args].concat()).unwrap()
+    }
+
+    #[test]
+    fn test_config() {
+        let example: Config = toml::from_str(include_str!("../config.example.toml")).unwrap();
+        assert!(example.validate().is_ok());
+        assert!(toml::from_str::<Config>("unknown = 1").is_err());
+
+        let config: Config = toml::from_str(
+            "bind_address = \"0.0.0.0:80\"\n\
+             [backends]\n\
+             regtest = \"tcp://localhost:50001\"\n\
+             [limits]\n\
+             max_inputs = 10\n\
+             [features]\n\
+             cache = false",
+        )
+        .unwrap();
+        assert_eq!(config.limits.max_inputs, 10);
+        assert_eq!(config.limits.max_addresses, 100);
+        assert!(!config.features.cache && config.features.jobs);
+
+        let config = config.with_overrides(cli(&[
+            "--port",
+            "8000",
+            "--backend",
+            "bitcoin=tcp://localhost:50002",
+            "--max-inputs",
+            "20",
+            "--disable",
+            "cache,jobs",
+        ]));
+        // the flags override the file
+        assert_eq!(config.bind_address, "0.0.0.0:8000");
+        assert_eq!(config.limits.max_inputs, 20);
+        assert!(!config.features.cache && !config.features.jobs);
+        assert_eq!(config.backends.len(), 2);
+        assert!(config.validate().is_ok());
+
+        let verifier = ReservesVerifier::from_config(&config, None).unwrap();
+        let (backend, network) = verifier
+            .backend_for(&["bcrt1qw508d6qejxtdg4y5r3zarvary0c5xw7kygt080".to_string()])
+            .unwrap();
+        assert_eq!(
+            (backend.name(), network),
+            ("tcp://localhost:50001", Network::Regtest)
+        );
+        let (backend, network) = verifier
+            .backend_for(&["not an address".to_string()])
+            .unwrap();
+        assert_eq!(
+            (backend.name(), network),
+            ("tcp://localhost:50002", Network::Bitcoin)
+        );
+    }
+
+    #[test]
+    fn test_validate() {
+        let mut config = Config::default();
+        config.confirmations.default = 2000;
+        assert!(config.validate().is_err());
+        config.confirmations.default = 3;
+        assert_eq!(config.confirmations.resolve(None), Ok(3));
+        assert_eq!(config.confirmations.resolve(Some(6)), Ok(6));
+        assert!(config.confirmations.resolve(Some(1001)).is_err());
+
+        config.tls.cert_file = Some("cert.pem".to_string());
+        assert!(config.validate().is_err());
+        config.tls.key_file = Some("key.pem".to_string());
+        assert!(config.validate().is_ok());
+
+        config.logging.level = "loud".to_string();
+        assert!(config.validate().is_err());
+        config.logging.level = "debug".to_string();
+
+        // monitors and webhooks would be open to anyone without keys
+        config.features.webhooks = true;
+        assert!(config.validate().is_err());
+        config.auth.keys_file = Some("keys.toml".to_string());
+        assert!(config.validate().is_ok());
+
+        config.attestation.key = Some("secret".to_string());
+        config.logging.redaction_key = Some("secret".to_string());
+        assert!(!config.to_redacted_toml().contains("secret"));
+        config.backends.clear();
+        assert!(config.validate().is_err());
+    }
+}
@@ -0,0 +1,377 @@
+//! Unsigned proofs of reserves of watch-only wallets, for the custodian to sign offline.
+//!
+//! Only public descriptors are accepted, the server never sees a private key.
+
+use crate::{ProvenUtxo, ReservesVerifier};
+use bdk_reserves::reserves::ProofOfReserves as _;
+use bdk_wallet::{
+    bitcoin::{
+        base64::{engine::general_purpose::STANDARD, Engine},
+        Address, Network, Psbt,
+    },
+    chain::ChainPosition,
+    miniscript::{descriptor::DescriptorPublicKey, Descriptor},
+    LocalOutput, Wallet,
+};
+use serde::{Deserialize, Serialize};
+use std::str::FromStr;
+
+/// Unused scripts in a row after which a wallet scan stops, unless requested otherwise.
+const DEFAULT_STOP_GAP: usize = 20;
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct UnsignedProofRequest {
+    pub descriptor: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub change_descriptor: Option<String>,
+    pub message: String,
+    /// The network of the wallet, bitcoin by default.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub network: Option<Network>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub stop_gap: Option<usize>,
+}
+
+/// A proof spending the challenge input and every confirmed utxo of the wallet, to be signed
+/// offline.
+#[derive(Debug, Serialize, Deserialize)]
+pub struct UnsignedProof {
+    pub proof_psbt: String,
+    /// The addresses of the utxos, to verify the signed proof with.
+    pub addresses: Vec<String>,
+    pub utxos: Vec<ProvenUtxo>,
+    pub amount: u64,
+}
+
+impl UnsignedProofRequest {
+    pub fn stop_gap(&self) -> usize {
+        self.stop_gap.unwrap_or(DEFAULT_STOP_GAP)
+    }
+}
+
+/// Scan a watch-only wallet through the backend of its network and build its unsigned proof of
+/// the utxos confirmed at the default confirmations, the ones a verification would count.
+pub fn unsigned_proof(
+    request: &UnsignedProofRequest,
+    verifier: &ReservesVerifier,
+) -> Result<UnsignedProof, String> {
+    check_watch_only(&request.descriptor)?;
+    if let Some(change_descriptor) = &request.change_descriptor {
+        check_watch_only(change_descriptor)?;
+    }
+    let network = request.network.unwrap_or(Network::Bitcoin);
+    let backend = verifier
+        .backends()
+        .get(&network)
+        .ok_or_else(|| format!("No backend for network {}", network))?;
+    let stop_gap = request.stop_gap();
+    let limits = verifier.limits();
+    limits.check_addresses(stop_gap)?;
+    let mut budget = limits.budget();
+
+    budget.spend()?;
+    let tip = backend.tip()?;
+    let confirmations = verifier.confirmations().default;
+    let block_height = tip.height.checked_sub(confirmations).ok_or_else(|| {
+        format!(
+            "Block height is below genesis, the tip {} has fewer than {} confirmations",
+            tip.height, confirmations
+        )
+    })?;
+
+    let descriptor = request.descriptor.clone();
+    let mut wallet = match request.change_descriptor.clone() {
+        Some(change_descriptor) => Wallet::create(descriptor, change_descriptor),
+        None => Wallet::create_single(descriptor),
+    }
+    .network(network)
+    .create_wallet_no_persist()
+    .map_err(|e| format!("Invalid descriptor: {:?}", e))?;
+    backend.full_scan(&mut wallet, stop_gap, &mut budget)?;
+
+    let (confirmed, unconfirmed): (Vec<_>, Vec<_>) =
+        wallet
+            .list_unspent()
+            .partition(|utxo| match utxo.chain_position {
+                ChainPosition::Confirmed { anchor, .. } => {
+                    anchor.block_id.height as usize <= block_height
+                }
+                ChainPosition::Unconfirmed { .. } => false,
+            });
+    let utxos = confirmed
+        .iter()
+        .map(|utxo| {
+            let address = Address::from_script(&utxo.txout.script_pubkey, network)
+                .map_err(|e| format!("Invalid address: {:?}", e))?;
+            Ok(ProvenUtxo {
+                address: address.to_string(),
+                outpoint: utxo.outpoint,
+                value: utxo.txout.value.to_sat(),
+            })
+        })
+        .collect::<Result<Vec<_>, String>>()?;
+    if utxos.is_empty() {
+        return Err(format!(
+            "No utxo found for the descriptor with {} confirmations",
+            confirmations
+        ));
+    }
+    // the challenge input comes on top of the utxos
+    limits.check_inputs(utxos.len() + 1)?;
+    let mut psbt = wallet
+        .create_proof(&request.message)
+        .map_err(|e| format!("{:?}", e))?;
+    remove_inputs(&mut psbt, &unconfirmed)?;
+
+    let mut addresses = Vec::new();
+    for utxo in &utxos {
+        if !addresses.contains(&utxo.address) {
+            addresses.push(utxo.address.clone());
+        }
+    }
+    Ok(UnsignedProof {
+        proof_psbt: STANDARD.encode(psbt.serialize()),
+        addresses,
+        amount: utxos.iter().map(|utxo| utxo.value).sum(),
+        utxos,
+    })
+}
+
+/// Take utxos out of a proof spending every utxo of the wallet, and their value out of its
+/// output, which must stay equal to the inputs.
+fn remove_inputs(psbt: &mut Psbt, utxos: &[LocalOutput]) -> Result<(), String> {
+    for utxo in utxos {
+        let Some(index) = psbt
+            .unsigned_tx
+            .input
+            .iter()
+            .position(|input| input.previous_output == utxo.outpoint)
+        else {
+            continue;
+        };
+        psbt.unsigned_tx.input.remove(index);
+        psbt.inputs.remove(index);
+        let output = &mut psbt.unsigned_tx.output[0];
+        output.value = output
+            .value
+            .checked_sub(utxo.txout.value)
+            .ok_or_else(|| "The proof output is below its inputs".to_string())?;
+    }
+    Ok(())
+}
+
+/// Refuse descriptors with private keys, which must stay with the custodian.
+fn check_watch_only(descriptor: &str) -> Result<(), String> {
+    // without the parse error, which may quote a private key
+    Descriptor::<DescriptorPublicKey>::from_str(descriptor)
+        .map(|_| ())
+        .map_err(|_| "Invalid watch-only descriptor, only public keys are accepted".to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        backend::{Backend, Tip, Unspent},
+        limits::{Budget, Limits},
+        verifier::Confirmations,
+        ProofOfReserves,
+    };
+    use bdk_wallet::{
+        bitcoin::{
+            absolute::LockTime, constants::genesis_block, hashes::Hash, transaction::Version,
+            Amount, BlockHash, OutPoint, Script, Transaction, TxIn, TxOut, Txid,
+        },
+        chain::{BlockId, CheckPoint, ConfirmationBlockTime, TxUpdate},
+        KeychainKind, SignOptions, Update,
+    };
+    use std::sync::Arc;
+
+    const DESCRIPTOR: &str = "wpkh(tprv8ZgxMBicQKsPcx5nBGsR63Pe8KnRUqmbJNENAfGftF3yuXoMMoVJJcYeUw5eVkm9WBPjWYt6HMWYJNesB5HaNVBaFc1M6dRjWSYnmewUMYy/84h/1h/0h/0/*)";
+    const TIP: u32 = 110;
+
+    /// Payments to the first addresses of the wallet, at their confirmation heights or
+    /// unconfirmed.
+    struct FakeChain {
+        payments: Vec<(Transaction, Option<u32>)>,
+    }
+
+    fn block(height: u32) -> BlockId {
+        let hash = match height {
+            0 => genesis_block(Network::Regtest).block_hash(),
+            _ => BlockHash::hash(&height.to_be_bytes()),
+        };
+        BlockId { height, hash }
+    }
+
+    impl FakeChain {
+        fn new(wallet: &Wallet, heights: &[Option<u32>]) -> Self {
+            let payments = heights
+                .iter()
+                .enumerate()
+                .map(|(index, height)| {
+                    let address = wallet.peek_address(KeychainKind::External, index as u32);
+                    let tx = Transaction {
+                        version: Version::TWO,
+                        lock_time: LockTime::ZERO,
+                        input: vec![TxIn {
+                            previous_output: OutPoint::new(Txid::all_zeros(), index as u32),
+                            ..Default::default()
+                        }],
+                        output: vec![TxOut {
+                            value: Amount::from_sat(10_000 * (index as u64 + 1)),
+                            script_pubkey: address.script_pubkey(),
+                        }],
+                    };
+                    (tx, *height)
+                })
+                .collect();
+            FakeChain { payments }
+        }
+    }
+
+    impl Backend for FakeChain {
+        fn name(&self) -> &str {
+            "fake"
+        }
+
+        fn tip(&self) -> Result<Tip, String> {
+            Ok(Tip {
+                height: TIP as usize,
+                hash: block(TIP).hash,
+                time: 0,
+            })
+        }
+
+        fn block_hash(&self, height: usize) -> Result<BlockHash, String> {
+            Ok(block(height as u32).hash)
+        }
+
+        fn unspent(&self, script: &Script) -> Result<Vec<Unspent>, String> {
+            Ok(self
+                .payments
+                .iter()
+                .filter(|(tx, _)| tx.output[0].script_pubkey == *script)
+                .map(|(tx, height)| Unspent {
+                    outpoint: OutPoint::new(tx.compute_txid(), 0),
+                    height: height.unwrap_or(0) as usize,
+                })
+                .collect())
+        }
+
+        fn transaction(&self, txid: &Txid) -> Result<Transaction, String> {
+            self.payments
+                .iter()
+                .map(|(tx, _)| tx)
+                .find(|tx| tx.compute_txid() == *txid)
+                .cloned()
+                .ok_or_else(|| format!("Unknown transaction {}", txid))
+        }
+
+        fn full_scan(
+            &self,
+            wallet: &mut Wallet,
+            _stop_gap: usize,
+            budget: &mut Budget,
+        ) -> Result<(), String> {
+            budget.spend()?;
+            let mut tx_update = TxUpdate::default();
+            let mut blocks = vec![block(0), block(TIP)];
+            for (tx, height) in &self.payments {
+                tx_update.txs.push(Arc::new(tx.clone()));
+                match height {
+                    Some(height) => {
+                        let anchor = ConfirmationBlockTime {
+                            block_id: block(*height),
+                            confirmation_time: 0,
+                        };
+                        tx_update.anchors.insert((anchor, tx.compute_txid()));
+                        blocks.push(block(*height));
+                    }
+                    None => {
+                        tx_update.seen_ats.insert(tx.compute_txid(), 0);
+                    }
+                }
+            }
+            blocks.sort_by_key(|block| block.height);
+            let update = Update {
+                last_active_indices: [(KeychainKind::External, self.payments.len() as u32 - 1)]
+                    .into(),
+                tx_update,
+                chain: Some(CheckPoint::from_block_ids(blocks).unwrap()),
+            };
+            wallet
+                .apply_update(update)
+                .map_err(|e| format!("Failed to apply wallet update: {:?}", e))
+        }
+    }
+
+    #[test]
+    fn test_round_trip() {
+        let mut signer = Wallet::create_single(DESCRIPTOR)
+            .network(Network::Regtest)
+            .create_wallet_no_persist()
+            .unwrap();
+        // only the first payment is confirmed at the default 3 confirmations
+        let chain = FakeChain::new(&signer, &[Some(100), Some(109), None]);
+        let mut budget = Limits::default().budget();
+        chain.full_scan(&mut signer, 20, &mut budget).unwrap();
+        let backend: Arc<dyn Backend> = Arc::new(chain);
+        let verifier = ReservesVerifier::new(
+            [(Network::Regtest, backend)].into(),
+            Limits::default(),
+            Confirmations::default(),
+            None,
+        )
+        .unwrap();
+
+        let request = UnsignedProofRequest {
+            descriptor: signer.public_descriptor(KeychainKind::External).to_string(),
+            change_descriptor: None,
+            message: "message".to_string(),
+            network: Some(Network::Regtest),
+            stop_gap: None,
+        };
+        let unsigned = unsigned_proof(&request, &verifier).unwrap();
+        assert_eq!(unsigned.amount, 10_000);
+        assert_eq!(unsigned.utxos.len(), 1);
+        assert_eq!(unsigned.addresses, vec![unsigned.utxos[0].address.clone()]);
+
+        let psbt = STANDARD.decode(&unsigned.proof_psbt).unwrap();
+        let mut psbt = Psbt::deserialize(&psbt).unwrap();
+        assert_eq!(psbt.unsigned_tx.input.len(), 2);
+        let sign_options = SignOptions {
+            trust_witness_utxo: true,
+            ..Default::default()
+        };
+        assert!(signer.sign(&mut psbt, sign_options).unwrap());
+
+        let report = verifier
+            .verify(&ProofOfReserves {
+                addresses: unsigned.addresses,
+                message: request.message.clone(),
+                proof_psbt: STANDARD.encode(psbt.serialize()),
+                claimed_amount: None,
+                confirmations: None,
+            })
+            .unwrap();
+        assert_eq!(report.reserves.spendable, 10_000);
+
+        // the scan is part of the budget of backend calls
+        let limits = Limits {
+            max_backend_calls: 1,
+            ..Limits::default()
+        };
+        let verifier = ReservesVerifier::new(
+            verifier.backends().clone(),
+            limits,
+            Confirmations::default(),
+            None,
+        )
+        .unwrap();
+        assert_eq!(
+            unsigned_proof(&request, &verifier).unwrap_err(),
+            "Too many backend calls, at most 1 are allowed per verification"
+        );
+    }
+}
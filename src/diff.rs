@@ -0,0 +1,112 @@
+//! Changes between two proofs of reserves, for example from consecutive reporting periods.
+
+use crate::{ProvenUtxo, Reserves};
+use serde::Serialize;
+use std::collections::HashSet;
+
+#[derive(Debug, PartialEq, Eq, Serialize)]
+pub struct ProofDiff {
+    pub block_height_before: usize,
+    pub block_height_after: usize,
+    pub added_addresses: Vec<String>,
+    pub removed_addresses: Vec<String>,
+    /// Utxos which were proven before, but are no longer part of the proof.
+    pub spent: Vec<ProvenUtxo>,
+    /// Utxos which are newly part of the proof.
+    pub created: Vec<ProvenUtxo>,
+    pub spendable_before: u64,
+    pub spendable_after: u64,
+    /// The change of the spendable amount in sats, negative if the reserves shrank.
+    pub net_change: i64,
+}
+
+/// Compare two verification results, attributing every change to an address or an outpoint.
+pub fn diff(before: &Reserves, after: &Reserves) -> ProofDiff {
+    let addresses_before = before.addresses.iter().collect::<HashSet<_>>();
+    let addresses_after = after.addresses.iter().collect::<HashSet<_>>();
+    let outpoints_before = before
+        .utxos
+        .iter()
+        .map(|utxo| utxo.outpoint)
+        .collect::<HashSet<_>>();
+    let outpoints_after = after
+        .utxos
+        .iter()
+        .map(|utxo| utxo.outpoint)
+        .collect::<HashSet<_>>();
+
+    ProofDiff {
+        block_height_before: before.block_height,
+        block_height_after: after.block_height,
+        added_addresses: after
+            .addresses
+            .iter()
+            .filter(|address| !addresses_before.contains(address))
+            .cloned()
+            .collect(),
+        removed_addresses: before
+            .addresses
+            .iter()
+            .filter(|address| !addresses_after.contains(address))
+            .cloned()
+            .collect(),
+        spent: before
+            .utxos
+            .iter()
+            .filter(|utxo| !outpoints_after.contains(&utxo.outpoint))
+            .cloned()
+            .collect(),
+        created: after
+            .utxos
+            .iter()
+            .filter(|utxo| !outpoints_before.contains(&utxo.outpoint))
+            .cloned()
+            .collect(),
+        spendable_before: before.spendable,
+        spendable_after: after.spendable,
+        net_change: after.spendable as i64 - before.spendable as i64,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use bdk_wallet::bitcoin::{hashes::Hash, BlockHash, OutPoint};
+    use std::str::FromStr;
+
+    fn utxo(address: &str, vout: u32, value: u64) -> ProvenUtxo {
+        ProvenUtxo {
+            address: address.to_string(),
+            outpoint: OutPoint::from_str(&format!(
+                "0000000000000000000000000000000000000000000000000000000000000001:{}",
+                vout
+            ))
+            .unwrap(),
+            value,
+        }
+    }
+
+    fn reserves(addresses: &[&str], utxos: Vec<ProvenUtxo>) -> Reserves {
+        Reserves {
+            spendable: utxos.iter().map(|utxo| utxo.value).sum(),
+            block_height: 100,
+            block_hash: BlockHash::all_zeros(),
+            addresses: addresses.iter().map(|a| a.to_string()).collect(),
+            utxos,
+            cached: false,
+        }
+    }
+
+    #[test]
+    fn test_diff() {
+        let before = reserves(&["a", "b"], vec![utxo("a", 0, 10), utxo("b", 1, 20)]);
+        let after = reserves(&["b", "c"], vec![utxo("b", 1, 20), utxo("c", 2, 5)]);
+
+        let diff = diff(&before, &after);
+        assert_eq!(diff.added_addresses, vec!["c".to_string()]);
+        assert_eq!(diff.removed_addresses, vec!["a".to_string()]);
+        assert_eq!(diff.spent, vec![utxo("a", 0, 10)]);
+        assert_eq!(diff.created, vec![utxo("c", 2, 5)]);
+        assert_eq!(diff.net_change, -5);
+    }
+}
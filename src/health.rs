@@ -0,0 +1,76 @@
+//! Readiness of the chain backends: each of them must answer and have a recent tip.
+
+use crate::{backend::Backend, metrics::metrics};
+use bdk_wallet::bitcoin::Network;
+use serde::Serialize;
+use std::time::Instant;
+
+#[derive(Debug, Serialize)]
+pub struct BackendStatus {
+    pub network: Network,
+    pub server: String,
+    pub ready: bool,
+    /// Time to get the tip, connecting first if needed.
+    pub latency_ms: u64,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub tip_height: Option<usize>,
+    /// Seconds since the tip was mined, by its header time.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub tip_age: Option<i64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+}
+
+/// Get the tip of a backend and check its age.
+pub fn probe(network: Network, backend: &dyn Backend, max_tip_age: u64, now: i64) -> BackendStatus {
+    let started = Instant::now();
+    let tip = backend.tip();
+    let mut status = BackendStatus {
+        network,
+        server: backend.name().to_string(),
+        ready: false,
+        latency_ms: started.elapsed().as_millis() as u64,
+        tip_height: None,
+        tip_age: None,
+        error: None,
+    };
+    match tip {
+        Ok(tip) => {
+            metrics().set_tip(network, tip.height);
+            let tip_age = now - tip.time as i64;
+            status.tip_height = Some(tip.height);
+            status.tip_age = Some(tip_age);
+            status.error = check_tip_age(tip_age, max_tip_age).err();
+            status.ready = status.error.is_none();
+        }
+        Err(e) => status.error = Some(e),
+    }
+    status
+}
+
+/// Whether the tip is recent enough, any tip is when the maximum age is 0.
+fn check_tip_age(tip_age: i64, max_tip_age: u64) -> Result<(), String> {
+    if max_tip_age > 0 && tip_age > max_tip_age as i64 {
+        return Err(format!(
+            "The tip is {} seconds old, at most {} are expected",
+            tip_age, max_tip_age
+        ));
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_check_tip_age() {
+        assert!(check_tip_age(600, 10800).is_ok());
+        assert!(check_tip_age(-10, 10800).is_ok());
+        assert_eq!(
+            check_tip_age(10801, 10800),
+            Err("The tip is 10801 seconds old, at most 10800 are expected".to_string())
+        );
+        assert!(check_tip_age(1_000_000, 0).is_ok());
+    }
+}
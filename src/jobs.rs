@@ -0,0 +1,161 @@
+//! Asynchronous verification jobs, run by a fixed number of worker threads.
+//!
+//! Jobs are stored before they are queued, so with a persistent database the jobs which were
+//! queued or still running when the server stopped are picked up again at startup.
+
+use crate::{storage::Storage, Progress, ProofOfReserves};
+use serde_json::json;
+use std::{
+    sync::{mpsc, Arc, Mutex},
+    thread,
+};
+use tracing::{error, info_span};
+
+/// Runs a verification, reporting its progress, and returns the answer of `/proof`.
+pub type Verifier =
+    dyn Fn(&ProofOfReserves, &mut dyn FnMut(Progress)) -> serde_json::Value + Send + Sync;
+
+pub struct Jobs {
+    storage: Arc<Storage>,
+    queue: mpsc::Sender<i64>,
+}
+
+impl Jobs {
+    /// Start the workers and queue the unfinished jobs of a previous run.
+    pub fn start(
+        storage: Arc<Storage>,
+        workers: usize,
+        verifier: Arc<Verifier>,
+    ) -> Result<Self, String> {
+        let (queue, receiver) = mpsc::channel();
+        let receiver = Arc::new(Mutex::new(receiver));
+        for _ in 0..workers.max(1) {
+            let storage = storage.clone();
+            let receiver = receiver.clone();
+            let verifier = verifier.clone();
+            thread::spawn(move || loop {
+                let id = match receiver.lock().unwrap().recv() {
+                    Ok(id) => id,
+                    Err(_) => return,
+                };
+                let _span = info_span!("job", job_id = id).entered();
+                if let Err(e) = run(&storage, &*verifier, id) {
+                    error!("{}", e);
+                }
+            });
+        }
+
+        for id in storage.unfinished_jobs()? {
+            queue
+                .send(id)
+                .map_err(|e| format!("Failed to queue job: {:?}", e))?;
+        }
+        Ok(Jobs { storage, queue })
+    }
+
+    /// Store and queue a verification, returning the id of the job.
+    pub fn submit(&self, proof: &ProofOfReserves) -> Result<i64, String> {
+        let request = serde_json::to_string(proof)
+            .map_err(|e| format!("Failed to serialize proof: {:?}", e))?;
+        let id = self.storage.insert_job(&request)?;
+        self.queue
+            .send(id)
+            .map_err(|e| format!("Failed to queue job: {:?}", e))?;
+        Ok(id)
+    }
+}
+
+fn run(storage: &Storage, verifier: &Verifier, id: i64) -> Result<(), String> {
+    let Some(job) = storage.get_job(id)? else {
+        return Ok(());
+    };
+    let proof: ProofOfReserves = match serde_json::from_str(&job.request) {
+        Ok(proof) => proof,
+        Err(e) => {
+            let result = json!({ "error": format!("Invalid job request: {:?}", e) });
+            return storage.update_job(id, "failed", None, Some(&result));
+        }
+    };
+
+    storage.update_job(id, "running", None, None)?;
+    let result = verifier(&proof, &mut |progress| {
+        if let Err(e) = storage.update_job(id, "running", Some(&json!(progress)), None) {
+            error!("{}", e);
+        }
+    });
+    let status = if result.get("error").is_some() {
+        "failed"
+    } else {
+        "completed"
+    };
+    storage.update_job(id, status, None, Some(&result))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::time::{Duration, Instant};
+
+    fn proof(message: &str) -> ProofOfReserves {
+        ProofOfReserves {
+            addresses: vec!["addr".to_string()],
+            message: message.to_string(),
+            proof_psbt: "psbt".to_string(),
+            claimed_amount: None,
+            confirmations: None,
+        }
+    }
+
+    fn wait_for(storage: &Storage, id: i64) -> crate::storage::JobRecord {
+        let start = Instant::now();
+        loop {
+            let job = storage.get_job(id).unwrap().unwrap();
+            if job.status == "completed" || job.status == "failed" {
+                return job;
+            }
+            assert!(
+                start.elapsed() < Duration::from_secs(10),
+                "job {} hangs",
+                id
+            );
+            thread::sleep(Duration::from_millis(10));
+        }
+    }
+
+    #[test]
+    fn test_jobs() {
+        let storage = Arc::new(Storage::open(None).unwrap());
+        // a job left over from a previous run
+        let left_over = storage
+            .insert_job(&serde_json::to_string(&proof("left over")).unwrap())
+            .unwrap();
+
+        let verifier: Arc<Verifier> = Arc::new(|proof, on_progress| {
+            on_progress(Progress::PsbtParsed { inputs: 2 });
+            on_progress(Progress::SignaturesChecked { spendable: 10 });
+            match proof.message.as_str() {
+                "invalid" => json!({ "error": "NonSpendableInput(1)" }),
+                message => json!({ "spendable": 10, "message": message }),
+            }
+        });
+        let jobs = Jobs::start(storage.clone(), 2, verifier).unwrap();
+
+        let job = wait_for(&storage, left_over);
+        assert_eq!(job.status, "completed");
+        assert_eq!(job.result.unwrap()["message"], "left over");
+
+        let id = jobs.submit(&proof("valid")).unwrap();
+        let job = wait_for(&storage, id);
+        assert_eq!(job.status, "completed");
+        assert_eq!(
+            job.progress,
+            Some(json!({ "step": "signatures_checked", "spendable": 10 }))
+        );
+        assert_eq!(job.result.unwrap()["spendable"], 10);
+
+        let id = jobs.submit(&proof("invalid")).unwrap();
+        let job = wait_for(&storage, id);
+        assert_eq!(job.status, "failed");
+        assert_eq!(job.result.unwrap()["error"], "NonSpendableInput(1)");
+    }
+}
@@ -0,0 +1,213 @@
+//! Proof of liabilities based on a Merkle-sum tree.
+//!
+//! Every leaf commits to a customer balance as
+//! `SHA256(0x00 || hashed_id || len(salt) || salt || balance)`, with the 32 bytes hashed id and
+//! the salt length and balance as 8 bytes big-endian. Every inner node commits to both children
+//! and their sums as `SHA256(0x01 || left.hash || left.sum || right.hash || right.sum)` and
+//! carries `left.sum + right.sum`. The sum of the root is therefore the total of all customer
+//! balances. The prefixes keep an inner node from passing for a leaf and the other way around.
+
+use bdk_wallet::bitcoin::{
+    hashes::{sha256, Hash, HashEngine},
+    hex::FromHex,
+};
+use serde::{Deserialize, Serialize};
+
+/// First byte hashed for a leaf.
+const LEAF_PREFIX: u8 = 0x00;
+/// First byte hashed for an inner node.
+const NODE_PREFIX: u8 = 0x01;
+
+/// A node of the Merkle-sum tree.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SumNode {
+    pub hash: sha256::Hash,
+    pub sum: u64,
+}
+
+/// The data of a single customer in the liabilities tree.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Leaf {
+    /// Hex encoded 32 bytes hash of the customer id.
+    pub hashed_id: String,
+    /// The balance of the customer in sats.
+    pub balance: u64,
+    /// Hex encoded salt, blinding the leaf hash.
+    pub salt: String,
+}
+
+/// On which side of the path the sibling node is.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Side {
+    Left,
+    Right,
+}
+
+/// A sibling node on the path from a leaf to the root.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PathStep {
+    pub hash: sha256::Hash,
+    pub sum: u64,
+    pub side: Side,
+}
+
+/// A leaf together with the path of siblings up to the root.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct InclusionProof {
+    pub leaf: Leaf,
+    pub path: Vec<PathStep>,
+}
+
+impl Leaf {
+    /// Compute the tree node for this leaf.
+    pub fn node(&self) -> Result<SumNode, String> {
+        let hashed_id = <[u8; 32]>::from_hex(&self.hashed_id)
+            .map_err(|e| format!("Invalid hashed id: {:?}", e))?;
+        let salt = Vec::<u8>::from_hex(&self.salt).map_err(|e| format!("Invalid salt: {:?}", e))?;
+
+        let mut engine = sha256::Hash::engine();
+        engine.input(&[LEAF_PREFIX]);
+        engine.input(&hashed_id);
+        engine.input(&(salt.len() as u64).to_be_bytes());
+        engine.input(&salt);
+        engine.input(&self.balance.to_be_bytes());
+        Ok(SumNode {
+            hash: sha256::Hash::from_engine(engine),
+            sum: self.balance,
+        })
+    }
+}
+
+/// Combine two child nodes into their parent.
+pub fn parent(left: &SumNode, right: &SumNode) -> Result<SumNode, String> {
+    let sum = left
+        .sum
+        .checked_add(right.sum)
+        .ok_or_else(|| "Sum overflow in liabilities tree".to_string())?;
+
+    let mut engine = sha256::Hash::engine();
+    engine.input(&[NODE_PREFIX]);
+    engine.input(left.hash.as_byte_array());
+    engine.input(&left.sum.to_be_bytes());
+    engine.input(right.hash.as_byte_array());
+    engine.input(&right.sum.to_be_bytes());
+    Ok(SumNode {
+        hash: sha256::Hash::from_engine(engine),
+        sum,
+    })
+}
+
+impl InclusionProof {
+    /// Recompute the root from the leaf and its path.
+    pub fn compute_root(&self) -> Result<SumNode, String> {
+        self.path.iter().try_fold(self.leaf.node()?, |node, step| {
+            let sibling = SumNode {
+                hash: step.hash,
+                sum: step.sum,
+            };
+            match step.side {
+                Side::Left => parent(&sibling, &node),
+                Side::Right => parent(&node, &sibling),
+            }
+        })
+    }
+
+    /// Check that the leaf is included in the tree with the given root.
+    pub fn verify(&self, root: &SumNode) -> Result<(), String> {
+        let computed = self.compute_root()?;
+        if computed.hash != root.hash {
+            return Err("Root hash mismatch".to_string());
+        }
+        if computed.sum != root.sum {
+            return Err(format!(
+                "Root sum mismatch: computed {} but the root claims {}",
+                computed.sum, root.sum
+            ));
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn leaf(id: &str, balance: u64) -> Leaf {
+        Leaf {
+            hashed_id: id.repeat(32),
+            balance,
+            salt: "00ff".to_string(),
+        }
+    }
+
+    #[test]
+    fn test_inclusion_proof() {
+        let (a, b, c) = (leaf("aa", 10), leaf("bb", 20), leaf("cc", 30));
+        let ab = parent(&a.node().unwrap(), &b.node().unwrap()).unwrap();
+        let root = parent(&ab, &c.node().unwrap()).unwrap();
+        assert_eq!(root.sum, 60);
+
+        let proof = InclusionProof {
+            leaf: b.clone(),
+            path: vec![
+                PathStep {
+                    hash: a.node().unwrap().hash,
+                    sum: 10,
+                    side: Side::Left,
+                },
+                PathStep {
+                    hash: c.node().unwrap().hash,
+                    sum: 30,
+                    side: Side::Right,
+                },
+            ],
+        };
+        assert_eq!(proof.verify(&root), Ok(()));
+
+        let mut inflated = proof.clone();
+        inflated.leaf.balance = 21;
+        assert!(inflated.verify(&root).is_err());
+
+        let mut wrong_sum = proof;
+        wrong_sum.path[1].sum = 29;
+        assert!(wrong_sum.verify(&root).is_err());
+    }
+
+    #[test]
+    fn test_invalid_leaf() {
+        assert!(leaf("not hex", 1).node().is_err());
+        // the hashed id is a hash, not any length
+        assert!(leaf("a", 1).node().is_err());
+    }
+
+    #[test]
+    fn test_leaf_encoding() {
+        // the same bytes cannot be split differently between the hashed id and the salt
+        let whole = Leaf {
+            hashed_id: "aa".repeat(32),
+            balance: 10,
+            salt: "bbcc".to_string(),
+        };
+        let split = Leaf {
+            hashed_id: "aa".repeat(31),
+            balance: 10,
+            salt: "aabbcc".to_string(),
+        };
+        assert!(whole.node().is_ok());
+        assert!(split.node().is_err());
+
+        // an inner node does not pass for a leaf
+        let (a, b) = (
+            leaf("aa", 10).node().unwrap(),
+            leaf("bb", 20).node().unwrap(),
+        );
+        let ab = parent(&a, &b).unwrap();
+        let forged = Leaf {
+            hashed_id: a.hash.to_string(),
+            balance: 30,
+            salt: String::new(),
+        };
+        assert_ne!(forged.node().unwrap().hash, ab.hash);
+    }
+}
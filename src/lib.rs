@@ -0,0 +1,82 @@
+//! Verification of bitcoin proofs of reserves, and the web service around it.
+//!
+//! The [`ReservesVerifier`] checks a proof against the chain, through a [`Backend`] per network,
+//! and can be embedded in other tools. Without the default `server` feature the crate is only
+//! this library:
+//!
+//! ```no_run
+//! use bdk_reserves_web::{
+//!     limits::Limits, Backend, Confirmations, ElectrumBackend, ProofOfReserves, ReservesVerifier,
+//! };
+//! use bdk_wallet::bitcoin::Network;
+//! use std::sync::Arc;
+//!
+//! # fn main() -> Result<(), String> {
+//! let backend: Arc<dyn Backend> =
+//!     Arc::new(ElectrumBackend::new("ssl://electrum.blockstream.info:50002", 5));
+//! let verifier = ReservesVerifier::new(
+//!     [(Network::Bitcoin, backend)].into(),
+//!     Limits::default(),
+//!     Confirmations::default(),
+//!     None,
+//! )?;
+//! let report = verifier.verify(&ProofOfReserves {
+//!     addresses: vec!["bc1qxy2kgdygjrsqtzq2n0yrf2493p83kkfjhx0wlh".to_string()],
+//!     message: "Proof of reserves of Example Custody".to_string(),
+//!     proof_psbt: "cHNidP8B...".to_string(),
+//!     claimed_amount: None,
+//!     confirmations: None,
+//! })?;
+//! # Ok(())
+//! # }
+//! ```
+
+pub mod backend;
+pub mod cache;
+pub mod construction;
+pub mod diff;
+pub mod liabilities;
+pub mod limits;
+pub mod metrics;
+pub mod verifier;
+
+// the web service, its configuration and the entry points of the binary
+#[cfg(feature = "server")]
+pub mod command;
+#[cfg(feature = "server")]
+pub mod config;
+#[cfg(feature = "server")]
+pub mod logging;
+#[cfg(feature = "server")]
+pub mod server;
+
+#[cfg(feature = "server")]
+mod attestation;
+#[cfg(feature = "server")]
+mod auth;
+#[cfg(feature = "server")]
+mod coalesce;
+#[cfg(feature = "server")]
+mod health;
+#[cfg(feature = "server")]
+mod jobs;
+#[cfg(feature = "server")]
+mod monitor;
+#[cfg(feature = "server")]
+mod permalink;
+#[cfg(feature = "server")]
+mod ratelimit;
+#[cfg(feature = "server")]
+mod storage;
+#[cfg(feature = "server")]
+mod tls;
+#[cfg(feature = "server")]
+mod transparency;
+#[cfg(feature = "server")]
+mod webhooks;
+
+pub use backend::{Backend, ElectrumBackend};
+pub use verifier::{
+    ClaimedAmount, Confirmations, Progress, ProofOfReserves, ProvenUtxo, Report, ReportingBlock,
+    Reserves, ReservesVerifier, Solvency, Verdict,
+};
@@ -0,0 +1,137 @@
+//! Caps on the work a single verification can cause on the server and the Electrum backend.
+
+use serde::{Deserialize, Serialize};
+
+/// The configurable limits of a verification.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct Limits {
+    pub max_addresses: usize,
+    pub max_inputs: usize,
+    /// Confirmed utxos of a single address, each of them costs a transaction lookup.
+    pub max_utxos_per_script: usize,
+    pub max_backend_calls: usize,
+}
+
+impl Default for Limits {
+    fn default() -> Self {
+        Limits {
+            max_addresses: 100,
+            max_inputs: 1000,
+            max_utxos_per_script: 1000,
+            max_backend_calls: 5000,
+        }
+    }
+}
+
+impl Limits {
+    pub fn check_addresses(&self, addresses: usize) -> Result<(), String> {
+        if addresses > self.max_addresses {
+            return Err(format!(
+                "Too many addresses: {}, at most {} are allowed",
+                addresses, self.max_addresses
+            ));
+        }
+        Ok(())
+    }
+
+    pub fn check_inputs(&self, inputs: usize) -> Result<(), String> {
+        if inputs > self.max_inputs {
+            return Err(format!(
+                "Too many proof inputs: {}, at most {} are allowed",
+                inputs, self.max_inputs
+            ));
+        }
+        Ok(())
+    }
+
+    pub fn check_utxos(&self, address: &str, utxos: usize) -> Result<(), String> {
+        if utxos > self.max_utxos_per_script {
+            return Err(format!(
+                "Too many utxos on address {}: {}, at most {} are allowed",
+                address, utxos, self.max_utxos_per_script
+            ));
+        }
+        Ok(())
+    }
+
+    /// A fresh budget of backend calls for one verification.
+    pub fn budget(&self) -> Budget {
+        Budget {
+            max: self.max_backend_calls,
+            used: 0,
+        }
+    }
+}
+
+/// Counts the backend calls of one verification.
+pub struct Budget {
+    max: usize,
+    used: usize,
+}
+
+impl Budget {
+    /// Account for the next backend call, failing once the budget is exhausted.
+    pub fn spend(&mut self) -> Result<(), String> {
+        self.spend_many(1)
+    }
+
+    /// Account for several backend calls made at once, like by a wallet scan.
+    pub fn spend_many(&mut self, calls: usize) -> Result<(), String> {
+        if calls > self.remaining() {
+            self.used = self.max;
+            return Err(self.exhausted());
+        }
+        self.used += calls;
+        Ok(())
+    }
+
+    /// The calls left before the budget is exhausted.
+    pub fn remaining(&self) -> usize {
+        self.max - self.used
+    }
+
+    /// The error of an exhausted budget.
+    pub fn exhausted(&self) -> String {
+        format!(
+            "Too many backend calls, at most {} are allowed per verification",
+            self.max
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_limits() {
+        let limits = Limits {
+            max_addresses: 2,
+            max_inputs: 3,
+            max_utxos_per_script: 4,
+            max_backend_calls: 2,
+        };
+        assert!(limits.check_addresses(2).is_ok());
+        assert_eq!(
+            limits.check_addresses(3),
+            Err("Too many addresses: 3, at most 2 are allowed".to_string())
+        );
+        assert!(limits.check_inputs(3).is_ok());
+        assert!(limits.check_inputs(4).is_err());
+        assert!(limits.check_utxos("addr", 4).is_ok());
+        assert!(limits.check_utxos("addr", 5).is_err());
+
+        let mut budget = limits.budget();
+        assert!(budget.spend().is_ok());
+        assert!(budget.spend().is_ok());
+        assert!(budget.spend().is_err());
+
+        let mut budget = limits.budget();
+        assert!(budget.spend_many(3).is_err());
+        assert_eq!(budget.remaining(), 0);
+        let mut budget = limits.budget();
+        assert!(budget.spend_many(2).is_ok());
+        assert!(budget.spend().is_err());
+    }
+}
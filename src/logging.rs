@@ -0,0 +1,221 @@
+//! Structured logging with a request ID on every line and redaction of custodial wallet data.
+//!
+//! Each request runs in a `request` span carrying its ID, taken from a valid `X-Request-Id`
+//! header or generated, and returned in the `X-Request-Id` response header.
+
+use crate::config::{Config, LogFormat, Logging, Redaction};
+use actix_web::{
+    body::MessageBody,
+    dev::{ServiceRequest, ServiceResponse},
+    http::header::{HeaderName, HeaderValue},
+    middleware::Next,
+    web, Error,
+};
+use bdk_wallet::bitcoin::{
+    hashes::{hmac, sha256, Hash, HashEngine},
+    secp256k1::rand,
+};
+use std::{
+    collections::hash_map::RandomState,
+    hash::BuildHasher,
+    str::FromStr,
+    sync::{
+        atomic::{AtomicU64, Ordering},
+        OnceLock,
+    },
+    time::Instant,
+};
+use tracing::{info, info_span, Instrument};
+use tracing_subscriber::filter::LevelFilter;
+
+const REQUEST_ID: HeaderName = HeaderName::from_static("x-request-id");
+
+static REDACTOR: OnceLock<Redactor> = OnceLock::new();
+
+struct Redactor {
+    redaction: Redaction,
+    key: Vec<u8>,
+}
+
+impl Redactor {
+    fn new(redaction: Redaction, key: Option<&str>) -> Self {
+        let key = match key {
+            Some(key) => key.as_bytes().to_vec(),
+            None => rand::random::<[u8; 32]>().to_vec(),
+        };
+        Redactor { redaction, key }
+    }
+
+    fn redact(&self, value: &str) -> String {
+        match self.redaction {
+            Redaction::Off => value.to_string(),
+            Redaction::Hash => {
+                let mut engine = hmac::HmacEngine::<sha256::Hash>::new(&self.key);
+                engine.input(value.as_bytes());
+                let hash = hmac::Hmac::<sha256::Hash>::from_engine(engine).to_string();
+                format!("hmac:{}", &hash[..16])
+            }
+            Redaction::Remove => "<redacted>".to_string(),
+        }
+    }
+}
+
+/// Log to stdout at the configured level and format, including the `log` records of the
+/// libraries.
+pub fn init(config: &Logging) -> Result<(), String> {
+    let level = level(config)?;
+    REDACTOR
+        .set(Redactor::new(
+            config.redaction,
+            config.redaction_key.as_deref(),
+        ))
+        .map_err(|_| "Logging is initialized already".to_string())?;
+    let builder = tracing_subscriber::fmt().with_max_level(level);
+    match config.format {
+        LogFormat::Json => builder
+            .json()
+            .flatten_event(true)
+            .with_current_span(true)
+            .with_span_list(false)
+            .try_init(),
+        LogFormat::Text => builder.try_init(),
+    }
+    .map_err(|e| format!("Failed to initialize logging: {:?}", e))
+}
+
+pub fn level(config: &Logging) -> Result<LevelFilter, String> {
+    LevelFilter::from_str(&config.level).map_err(|_| format!("Invalid log level {}", config.level))
+}
+
+/// An address, PSBT or other custodial wallet data as it may be logged.
+pub fn redact(value: &str) -> String {
+    REDACTOR
+        .get_or_init(|| Redactor::new(Redaction::default(), None))
+        .redact(value)
+}
+
+pub fn redact_all(values: &[String]) -> Vec<String> {
+    values.iter().map(|value| redact(value)).collect()
+}
+
+fn valid_request_id(id: &str) -> bool {
+    (1..=64).contains(&id.len())
+        && id
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || matches!(c, '-' | '_' | '.'))
+}
+
+fn new_request_id() -> String {
+    static HASHER: OnceLock<RandomState> = OnceLock::new();
+    static COUNTER: AtomicU64 = AtomicU64::new(0);
+    let count = COUNTER.fetch_add(1, Ordering::Relaxed);
+    format!(
+        "{:016x}",
+        HASHER.get_or_init(RandomState::new).hash_one(count)
+    )
+}
+
+/// Middleware running each request in a span with its ID and logging it once answered.
+pub async fn middleware(
+    req: ServiceRequest,
+    next: Next<impl MessageBody + 'static>,
+) -> Result<ServiceResponse<impl MessageBody>, Error> {
+    let request_id = req
+        .headers()
+        .get(&REQUEST_ID)
+        .and_then(|id| id.to_str().ok())
+        .filter(|id| valid_request_id(id))
+        .map(str::to_string)
+        .unwrap_or_else(new_request_id);
+    let access_log = req
+        .app_data::<web::Data<Config>>()
+        .map(|config| config.logging.access_log)
+        .unwrap_or(true);
+    let span = info_span!("request", request_id = %request_id);
+    let method = req.method().clone();
+    // without the query, which may contain addresses
+    let path = req.path().to_string();
+    let started = Instant::now();
+
+    let mut response = next.call(req).instrument(span.clone()).await?;
+    if access_log {
+        span.in_scope(|| {
+            info!(
+                method = %method,
+                path = %path,
+                status = response.status().as_u16(),
+                duration_ms = started.elapsed().as_millis() as u64,
+                "Request"
+            )
+        });
+    }
+    if let Ok(value) = HeaderValue::from_str(&request_id) {
+        response.headers_mut().insert(REQUEST_ID, value);
+    }
+    Ok(response)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use actix_web::{middleware, test, App, HttpResponse};
+
+    #[actix_web::test]
+    async fn test_redact() {
+        // hashed unless configured otherwise
+        let redacted = redact("bc1qsecretaddress");
+        assert!(redacted.starts_with("hmac:"));
+        assert!(!redacted.contains("secret"));
+        assert_eq!(redact("bc1qsecretaddress"), redacted);
+        assert_ne!(redact("bc1qotheraddress"), redacted);
+
+        // keyed, so the hash of a known address doesn't match without the key
+        let keyed = Redactor::new(Redaction::Hash, Some("deployment secret"));
+        let hash = keyed.redact("bc1qsecretaddress");
+        assert_eq!(
+            Redactor::new(Redaction::Hash, Some("deployment secret")).redact("bc1qsecretaddress"),
+            hash
+        );
+        assert_ne!(
+            Redactor::new(Redaction::Hash, Some("other secret")).redact("bc1qsecretaddress"),
+            hash
+        );
+        let unkeyed = sha256::Hash::hash(b"bc1qsecretaddress").to_string();
+        assert!(!hash.contains(&unkeyed[..16]));
+        assert_ne!(
+            Redactor::new(Redaction::Hash, None).redact("bc1qsecretaddress"),
+            Redactor::new(Redaction::Hash, None).redact("bc1qsecretaddress")
+        );
+        assert_eq!(
+            Redactor::new(Redaction::Remove, None).redact("bc1qsecretaddress"),
+            "<redacted>"
+        );
+
+        assert!(valid_request_id("6f1c-42_a.b"));
+        assert!(!valid_request_id(""));
+        assert!(!valid_request_id("id with spaces"));
+        assert!(!valid_request_id(&"a".repeat(65)));
+        assert_ne!(new_request_id(), new_request_id());
+    }
+
+    #[actix_web::test]
+    async fn test_request_id() {
+        let app = App::new()
+            .wrap(middleware::from_fn(middleware))
+            .route("/", web::get().to(HttpResponse::Ok));
+        let app = test::init_service(app).await;
+
+        let req = test::TestRequest::get()
+            .insert_header(("X-Request-Id", "client-id"))
+            .to_request();
+        let resp = test::call_service(&app, req).await;
+        assert_eq!(resp.headers().get(REQUEST_ID).unwrap(), "client-id");
+
+        let req = test::TestRequest::get()
+            .insert_header(("X-Request-Id", "not valid"))
+            .to_request();
+        let resp = test::call_service(&app, req).await;
+        let id = resp.headers().get(REQUEST_ID).unwrap().to_str().unwrap();
+        assert!(valid_request_id(id) && id != "not valid");
+    }
+}
@@ -0,0 +1,286 @@
+//! Prometheus metrics of the verifications, the Electrum backends, the cache and the jobs.
+//!
+//! The metrics are process wide, like the backends and the cache they describe.
+
+use bdk_wallet::bitcoin::Network;
+use prometheus_client::{
+    encoding::{text::encode, EncodeLabelSet},
+    metrics::{
+        counter::Counter,
+        family::Family,
+        gauge::Gauge,
+        histogram::{exponential_buckets, Histogram},
+    },
+    registry::Registry,
+};
+use std::{
+    sync::OnceLock,
+    time::{Duration, Instant},
+};
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash, EncodeLabelSet)]
+struct VerificationLabels {
+    /// `verified`, `insolvent` or `error`.
+    outcome: &'static str,
+    code: String,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash, EncodeLabelSet)]
+struct BackendLabels {
+    server: String,
+    call: &'static str,
+    result: &'static str,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash, EncodeLabelSet)]
+struct ServerLabels {
+    server: String,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash, EncodeLabelSet)]
+struct CacheLabels {
+    kind: &'static str,
+    result: &'static str,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash, EncodeLabelSet)]
+struct JobLabels {
+    status: &'static str,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash, EncodeLabelSet)]
+struct NetworkLabels {
+    network: String,
+}
+
+pub struct Metrics {
+    registry: Registry,
+    verifications: Family<VerificationLabels, Counter>,
+    verification_duration: Histogram,
+    backend_calls: Family<BackendLabels, Counter>,
+    backend_call_duration: Family<ServerLabels, Histogram>,
+    cache_lookups: Family<CacheLabels, Counter>,
+    jobs: Family<JobLabels, Gauge>,
+    tip_height: Family<NetworkLabels, Gauge>,
+}
+
+impl Default for Metrics {
+    fn default() -> Self {
+        let mut metrics = Metrics {
+            registry: Registry::with_prefix("bdk_reserves"),
+            verifications: Family::default(),
+            verification_duration: Histogram::new(exponential_buckets(0.05, 2.0, 12)),
+            backend_calls: Family::default(),
+            backend_call_duration: Family::new_with_constructor(|| {
+                Histogram::new(exponential_buckets(0.005, 2.0, 12))
+            }),
+            cache_lookups: Family::default(),
+            jobs: Family::default(),
+            tip_height: Family::default(),
+        };
+        metrics.registry.register(
+            "verifications",
+            "Verifications of proofs of reserves by outcome and error code",
+            metrics.verifications.clone(),
+        );
+        metrics.registry.register(
+            "verification_duration_seconds",
+            "Time to verify a proof of reserves",
+            metrics.verification_duration.clone(),
+        );
+        metrics.registry.register(
+            "backend_calls",
+            "Calls to the Electrum backends by server, call and result",
+            metrics.backend_calls.clone(),
+        );
+        metrics.registry.register(
+            "backend_call_duration_seconds",
+            "Time of the calls to the Electrum backends by server",
+            metrics.backend_call_duration.clone(),
+        );
+        metrics.registry.register(
+            "cache_lookups",
+            "Lookups in the cache of results and utxos by hit or miss",
+            metrics.cache_lookups.clone(),
+        );
+        metrics.registry.register(
+            "jobs",
+            "Verification jobs waiting or running",
+            metrics.jobs.clone(),
+        );
+        metrics.registry.register(
+            "tip_height",
+            "The last seen chain tip height per network",
+            metrics.tip_height.clone(),
+        );
+        metrics
+    }
+}
+
+impl Metrics {
+    /// Count a verification by the outcome of its answer.
+    pub fn record_verification(&self, answer: &serde_json::Value, duration: Duration) {
+        let labels = match answer.get("error").and_then(|error| error.as_str()) {
+            Some(error) => VerificationLabels {
+                outcome: "error",
+                code: error_code(error),
+            },
+            None if answer["solvency"]["verdict"] == "fail" => VerificationLabels {
+                outcome: "insolvent",
+                code: String::new(),
+            },
+            None => VerificationLabels {
+                outcome: "verified",
+                code: String::new(),
+            },
+        };
+        self.verifications.get_or_create(&labels).inc();
+        self.verification_duration.observe(duration.as_secs_f64());
+    }
+
+    /// Make a call to a backend, counting and timing it.
+    pub fn backend_call<T, E>(
+        &self,
+        server: &str,
+        call: &'static str,
+        f: impl FnOnce() -> Result<T, E>,
+    ) -> Result<T, E> {
+        let started = Instant::now();
+        let result = f();
+        self.backend_call_duration
+            .get_or_create(&ServerLabels {
+                server: server.to_string(),
+            })
+            .observe(started.elapsed().as_secs_f64());
+        self.backend_calls
+            .get_or_create(&BackendLabels {
+                server: server.to_string(),
+                call,
+                result: if result.is_ok() { "ok" } else { "error" },
+            })
+            .inc();
+        result
+    }
+
+    pub fn cache_lookup(&self, kind: &'static str, hit: bool) {
+        self.cache_lookups
+            .get_or_create(&CacheLabels {
+                kind,
+                result: if hit { "hit" } else { "miss" },
+            })
+            .inc();
+    }
+
+    pub fn set_tip(&self, network: Network, height: usize) {
+        self.tip_height
+            .get_or_create(&NetworkLabels {
+                network: network.to_string(),
+            })
+            .set(height as i64);
+    }
+
+    /// The metrics in the OpenMetrics text format, with the given number of jobs per status.
+    pub fn encode(&self, jobs: &[(String, i64)]) -> Result<String, String> {
+        for status in ["queued", "running"] {
+            let count = jobs
+                .iter()
+                .find(|(job_status, _)| job_status == status)
+                .map_or(0, |(_, count)| *count);
+            self.jobs.get_or_create(&JobLabels { status }).set(count);
+        }
+        let mut body = String::new();
+        encode(&mut body, &self.registry)
+            .map_err(|e| format!("Failed to encode metrics: {:?}", e))?;
+        Ok(body)
+    }
+}
+
+/// The metrics of the process.
+pub fn metrics() -> &'static Metrics {
+    static METRICS: OnceLock<Metrics> = OnceLock::new();
+    METRICS.get_or_init(Metrics::default)
+}
+
+/// A short code for an error message, to count errors without their details: the variant of
+/// a proof error like `NonSpendableInput(1)`, or the kind of the other errors. Logs carry
+/// the code rather than the message, which may quote addresses and outpoints.
+pub fn error_code(error: &str) -> String {
+    const CODES: &[(&str, &str)] = &[
+        ("Base64 decode error", "invalid_psbt"),
+        ("PSBT deserialization error", "invalid_psbt"),
+        ("No address provided", "no_address"),
+        ("Invalid address", "invalid_address"),
+        ("Invalid claimed amount", "invalid_claimed_amount"),
+        ("Too many backend calls", "too_many_backend_calls"),
+        ("Too many", "limit_exceeded"),
+        ("Block height", "invalid_block_height"),
+        ("Failed to", "backend_error"),
+    ];
+    if let Some((_, code)) = CODES.iter().find(|(prefix, _)| error.starts_with(prefix)) {
+        return code.to_string();
+    }
+    let variant = error
+        .split(['(', '{'])
+        .next()
+        .unwrap_or_default()
+        .trim_end();
+    if variant.is_empty() || !variant.chars().all(|c| c.is_ascii_alphanumeric()) {
+        return "other".to_string();
+    }
+    let mut code = String::new();
+    for (i, c) in variant.chars().enumerate() {
+        if c.is_ascii_uppercase() && i > 0 {
+            code.push('_');
+        }
+        code.push(c.to_ascii_lowercase());
+    }
+    code
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_metrics() {
+        assert_eq!(error_code("NonSpendableInput(1)"), "non_spendable_input");
+        assert_eq!(error_code("InvalidOutput"), "invalid_output");
+        assert_eq!(error_code("Base64 decode error: x"), "invalid_psbt");
+        assert_eq!(
+            error_code("Too many addresses: 3, at most 2 are allowed"),
+            "limit_exceeded"
+        );
+        assert_eq!(error_code("Something broke"), "other");
+
+        let metrics = Metrics::default();
+        let second = Duration::from_secs(1);
+        metrics.record_verification(&json!({ "error": "NonSpendableInput(1)" }), second);
+        metrics.record_verification(&json!({ "spendable": 10 }), second);
+        metrics.record_verification(
+            &json!({ "spendable": 10, "solvency": { "verdict": "fail" } }),
+            second,
+        );
+        assert!(metrics
+            .backend_call("ssl://server", "block_header", || Err::<(), _>(()))
+            .is_err());
+        metrics.cache_lookup("result", true);
+        metrics.set_tip(Network::Testnet, 800_000);
+
+        let body = metrics.encode(&[("queued".to_string(), 2)]).unwrap();
+        for line in [
+            "bdk_reserves_verifications_total{outcome=\"error\",code=\"non_spendable_input\"} 1",
+            "bdk_reserves_verifications_total{outcome=\"verified\",code=\"\"} 1",
+            "bdk_reserves_verifications_total{outcome=\"insolvent\",code=\"\"} 1",
+            "bdk_reserves_verification_duration_seconds_count 3",
+            "bdk_reserves_backend_calls_total{server=\"ssl://server\",call=\"block_header\",result=\"error\"} 1",
+            "bdk_reserves_backend_call_duration_seconds_count{server=\"ssl://server\"} 1",
+            "bdk_reserves_cache_lookups_total{kind=\"result\",result=\"hit\"} 1",
+            "bdk_reserves_jobs{status=\"queued\"} 2",
+            "bdk_reserves_jobs{status=\"running\"} 0",
+            "bdk_reserves_tip_height{network=\"testnet\"} 800000",
+        ] {
+            assert!(body.contains(line), "{} not in {}", line, body);
+        }
+    }
+}
@@ -0,0 +1,248 @@
+//! Periodic re-verification of registered proofs.
+//!
+//! A proof which verified once keeps the list of utxos it proved. When the proof stops verifying,
+//! typically because some of those utxos were spent, the monitor looks them up individually and
+//! keeps counting the ones which are still unspent towards the spendable amount.
+
+use crate::{
+    backend::Backend,
+    metrics::{error_code, metrics},
+    storage::{now, MonitorRecord, Storage},
+    webhooks::Webhooks,
+    ReportingBlock, ReservesVerifier,
+};
+use bdk_wallet::bitcoin::Network;
+use serde_json::json;
+use std::{collections::HashMap, sync::Arc, thread, time::Duration};
+use tracing::{error, info, warn};
+
+/// How often the monitor looks for new blocks and due checks.
+const TICK: Duration = Duration::from_secs(30);
+
+/// Start checking the registered monitors in the background.
+pub fn spawn(storage: Arc<Storage>, webhooks: Option<Webhooks>, verifier: Arc<ReservesVerifier>) {
+    thread::spawn(move || loop {
+        if let Err(e) = run_due(&storage, webhooks.as_ref(), &verifier) {
+            error!("Monitor: {}", e);
+        }
+        thread::sleep(TICK);
+    });
+}
+
+fn run_due(
+    storage: &Storage,
+    webhooks: Option<&Webhooks>,
+    verifier: &ReservesVerifier,
+) -> Result<(), String> {
+    let mut tips = HashMap::new();
+    for monitor in storage.list_monitors()? {
+        let (backend, network) = verifier.backend_for(&monitor.addresses)?;
+        let tip = match tips.get(backend.name()) {
+            Some(tip) => *tip,
+            None => match current_tip(backend.as_ref(), network) {
+                Ok(tip) => *tips.entry(backend.name()).or_insert(tip),
+                Err(e) => {
+                    warn!(
+                        monitor_id = monitor.id,
+                        error = error_code(&e),
+                        "Monitor tip unavailable"
+                    );
+                    continue;
+                }
+            },
+        };
+        if is_due(&monitor, tip, now(), verifier.confirmations().default) {
+            let id = monitor.id;
+            if let Err(e) = check(storage, webhooks, verifier, monitor, tip) {
+                warn!(
+                    monitor_id = id,
+                    error = error_code(&e),
+                    "Monitor check failed"
+                );
+            }
+        }
+    }
+    Ok(())
+}
+
+/// Whether the interval elapsed, or a new block arrived for monitors without interval.
+fn is_due(monitor: &MonitorRecord, tip: usize, now: i64, confirmations: usize) -> bool {
+    match (
+        monitor.interval,
+        monitor.last_checked_at,
+        monitor.last_block_height,
+    ) {
+        (_, None, _) | (None, _, None) => true,
+        (Some(interval), Some(last_checked_at), _) => now - last_checked_at >= interval as i64,
+        (None, Some(_), Some(height)) => tip.saturating_sub(confirmations) as i64 > height,
+    }
+}
+
+/// Re-verify the proof of a monitor and raise alerts for what changed since the last check.
+fn check(
+    storage: &Storage,
+    webhooks: Option<&Webhooks>,
+    verifier: &ReservesVerifier,
+    mut monitor: MonitorRecord,
+    tip: usize,
+) -> Result<(), String> {
+    let confirmations = verifier.confirmations().default;
+    let mut block_height = tip.saturating_sub(confirmations);
+    let verification = verifier.reserves(
+        &monitor.message,
+        &monitor.proof_psbt,
+        ReportingBlock::Confirmations(confirmations),
+        &monitor.addresses,
+        &mut |_| {},
+    );
+
+    let spent = match verification {
+        Ok(reserves) => {
+            monitor.status = "ok".to_string();
+            monitor.spendable = Some(reserves.spendable);
+            monitor.proven_utxos = reserves.utxos;
+            block_height = reserves.block_height;
+            Vec::new()
+        }
+        Err(e) => {
+            if monitor.status != "failing" {
+                alert(
+                    storage,
+                    webhooks,
+                    &monitor,
+                    "verification_failed",
+                    json!({ "error": e }),
+                );
+            }
+            monitor.status = "failing".to_string();
+            if monitor.proven_utxos.is_empty() {
+                Vec::new()
+            } else {
+                let unspent = verifier.unspent_outpoints(&monitor.addresses)?;
+                let (unspent, spent): (Vec<_>, Vec<_>) = monitor
+                    .proven_utxos
+                    .iter()
+                    .cloned()
+                    .partition(|utxo| unspent.contains(&utxo.outpoint));
+                monitor.spendable = Some(unspent.iter().map(|utxo| utxo.value).sum());
+                spent
+                    .into_iter()
+                    .filter(|utxo| !monitor.spent_outpoints.contains(&utxo.outpoint))
+                    .collect()
+            }
+        }
+    };
+
+    for utxo in spent {
+        alert(storage, webhooks, &monitor, "utxo_spent", json!(utxo));
+        monitor.spent_outpoints.push(utxo.outpoint);
+    }
+    if let Some(detail) = floor_alert(&mut monitor) {
+        alert(storage, webhooks, &monitor, "below_floor", detail);
+    }
+
+    monitor.last_block_height = Some(block_height as i64);
+    monitor.last_checked_at = Some(now());
+    storage.update_monitor(&monitor)
+}
+
+/// Update whether the monitor is below its floor, returning the alert when it just fell below.
+fn floor_alert(monitor: &mut MonitorRecord) -> Option<serde_json::Value> {
+    let (Some(floor), Some(spendable)) = (monitor.floor, monitor.spendable) else {
+        return None;
+    };
+    let was_below_floor = monitor.below_floor;
+    monitor.below_floor = spendable < floor;
+    (monitor.below_floor && !was_below_floor).then(|| {
+        json!({
+            "spendable": spendable,
+            "floor": floor,
+            "shortfall": floor - spendable,
+        })
+    })
+}
+
+fn alert(
+    storage: &Storage,
+    webhooks: Option<&Webhooks>,
+    monitor: &MonitorRecord,
+    kind: &str,
+    detail: serde_json::Value,
+) {
+    // the detail may name addresses, it is in the events of the monitor
+    info!(monitor_id = monitor.id, kind, "Monitor alert");
+    if let Err(e) = storage.insert_monitor_event(monitor.id, kind, &detail) {
+        error!(monitor_id = monitor.id, "{}", e);
+    }
+    if let Some(webhooks) = webhooks {
+        webhooks.notify(
+            kind,
+            json!({ "monitor_id": monitor.id, "message": monitor.message, "detail": detail }),
+        );
+    }
+}
+
+fn current_tip(backend: &dyn Backend, network: Network) -> Result<usize, String> {
+    let tip = backend.tip()?.height;
+    metrics().set_tip(network, tip);
+    Ok(tip)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn monitor(floor: Option<u64>, interval: Option<u64>) -> MonitorRecord {
+        MonitorRecord {
+            id: 1,
+            proof_psbt: String::new(),
+            message: String::new(),
+            addresses: Vec::new(),
+            floor,
+            interval,
+            status: "ok".to_string(),
+            spendable: None,
+            proven_utxos: Vec::new(),
+            spent_outpoints: Vec::new(),
+            below_floor: false,
+            last_block_height: Some(100),
+            last_checked_at: Some(1000),
+            created_at: 0,
+        }
+    }
+
+    #[test]
+    fn test_is_due() {
+        let every_block = monitor(None, None);
+        assert!(!is_due(&every_block, 103, 5000, 3));
+        assert!(is_due(&every_block, 104, 1001, 3));
+
+        let hourly = monitor(None, Some(3600));
+        assert!(!is_due(&hourly, 200, 4599, 3));
+        assert!(is_due(&hourly, 100, 4600, 3));
+
+        let mut new = monitor(None, Some(3600));
+        new.last_checked_at = None;
+        assert!(is_due(&new, 0, 0, 3));
+    }
+
+    #[test]
+    fn test_floor_alert() {
+        let mut monitor = monitor(Some(100), None);
+        monitor.spendable = Some(150);
+        assert_eq!(floor_alert(&mut monitor), None);
+
+        monitor.spendable = Some(80);
+        assert_eq!(
+            floor_alert(&mut monitor),
+            Some(json!({ "spendable": 80, "floor": 100, "shortfall": 20 }))
+        );
+        assert!(monitor.below_floor);
+        // only alert once while staying below the floor
+        assert_eq!(floor_alert(&mut monitor), None);
+
+        monitor.spendable = Some(100);
+        assert_eq!(floor_alert(&mut monitor), None);
+        assert!(!monitor.below_floor);
+    }
+}
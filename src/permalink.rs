@@ -0,0 +1,198 @@
+//! Content-addressed, read-only pages for verification results.
+
+use crate::storage::VerificationRecord;
+use bdk_wallet::bitcoin::hashes::{sha256, Hash, HashEngine};
+
+/// Fields of a report which differ between identical verifications.
+const VOLATILE_FIELDS: [&str; 1] = ["cached"];
+
+/// The permalink id of a result: the hash of the request together with the report, without its
+/// volatile fields.
+pub fn result_id(request_hash: &str, report: &serde_json::Value) -> String {
+    let mut report = report.clone();
+    if let Some(fields) = report.as_object_mut() {
+        for field in VOLATILE_FIELDS {
+            fields.remove(field);
+        }
+    }
+    let mut engine = sha256::Hash::engine();
+    engine.input(request_hash.as_bytes());
+    engine.input(report.to_string().as_bytes());
+    sha256::Hash::from_engine(engine).to_string()
+}
+
+/// Render the permalink page of a verification.
+pub fn render(id: &str, record: &VerificationRecord) -> String {
+    let report = &record.report;
+    let summary = match (report.get("error"), report.get("spendable")) {
+        (Some(error), _) => format!("Verification failed: {}", text(error)),
+        (None, Some(spendable)) => match report.get("solvency") {
+            Some(solvency) => format!(
+                "Spendable: {} sats, claim of {} sats: {}",
+                spendable,
+                solvency["claimed_amount"],
+                text(&solvency["verdict"])
+            ),
+            None => format!("Spendable: {} sats", spendable),
+        },
+        (None, None) => "Unknown result".to_string(),
+    };
+    let utxos = report
+        .get("utxos")
+        .and_then(|utxos| utxos.as_array())
+        .map(|utxos| {
+            utxos
+                .iter()
+                .map(|utxo| {
+                    format!(
+                        "<tr><td>{}</td><td>{}</td><td>{}</td></tr>",
+                        escape_html(&text(&utxo["address"])),
+                        escape_html(&text(&utxo["outpoint"])),
+                        utxo["value"]
+                    )
+                })
+                .collect::<String>()
+        })
+        .unwrap_or_default();
+    let report = serde_json::to_string_pretty(report).unwrap_or_default();
+
+    let anchor_height = record
+        .anchor_height
+        .map(|height| height.to_string())
+        .unwrap_or_default();
+
+    fill_template(
+        include_str!("../res/permalink.html"),
+        &[
+            ("id", escape_html(id)),
+            ("outcome", escape_html(&record.outcome)),
+            ("summary", escape_html(&summary)),
+            ("created_at", format_timestamp(record.created_at)),
+            ("message", escape_html(&record.message)),
+            ("addresses", escape_html(&record.addresses.join(", "))),
+            ("anchor_height", anchor_height),
+            (
+                "anchor_hash",
+                escape_html(record.anchor_hash.as_deref().unwrap_or_default()),
+            ),
+            ("backend", escape_html(&record.backend)),
+            ("request_hash", escape_html(&record.request_hash)),
+            ("utxos", utxos),
+            ("report", escape_html(&report)),
+        ],
+    )
+}
+
+/// Replace every `{{key}}` in the template in a single pass, so values are never expanded again.
+fn fill_template(template: &str, values: &[(&str, String)]) -> String {
+    let mut html = String::with_capacity(template.len());
+    let mut rest = template;
+    while let Some(start) = rest.find("{{") {
+        html.push_str(&rest[..start]);
+        rest = &rest[start..];
+        let Some(end) = rest.find("}}") else {
+            break;
+        };
+        match values.iter().find(|(key, _)| *key == &rest[2..end]) {
+            Some((_, value)) => html.push_str(value),
+            None => html.push_str(&rest[..end + 2]),
+        }
+        rest = &rest[end + 2..];
+    }
+    html.push_str(rest);
+    html
+}
+
+/// A json value as plain text, without the quotes around strings.
+fn text(value: &serde_json::Value) -> String {
+    match value {
+        serde_json::Value::String(s) => s.clone(),
+        other => other.to_string(),
+    }
+}
+
+fn escape_html(s: &str) -> String {
+    s.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+        .replace('\'', "&#39;")
+}
+
+/// Format a unix timestamp as "YYYY-MM-DD HH:MM:SS" in UTC.
+fn format_timestamp(timestamp: i64) -> String {
+    let days = timestamp.div_euclid(86400);
+    let seconds = timestamp.rem_euclid(86400);
+
+    // civil date from days since 1970-01-01, see http://howardhinnant.github.io/date_algorithms.html
+    let z = days + 719468;
+    let era = z.div_euclid(146097);
+    let doe = z.rem_euclid(146097);
+    let yoe = (doe - doe / 1460 + doe / 36524 - doe / 146096) / 365;
+    let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+    let mp = (5 * doy + 2) / 153;
+    let day = doy - (153 * mp + 2) / 5 + 1;
+    let month = if mp < 10 { mp + 3 } else { mp - 9 };
+    let year = yoe + era * 400 + i64::from(month <= 2);
+
+    format!(
+        "{:04}-{:02}-{:02} {:02}:{:02}:{:02}",
+        year,
+        month,
+        day,
+        seconds / 3600,
+        seconds % 3600 / 60,
+        seconds % 60
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_result_id() {
+        let report = json!({ "spendable": 10 });
+        assert_eq!(result_id("hash", &report), result_id("hash", &report));
+        assert_ne!(result_id("hash", &report), result_id("other", &report));
+        assert_ne!(
+            result_id("hash", &report),
+            result_id("hash", &json!({ "spendable": 11 }))
+        );
+        // results served from the cache share the permalink
+        assert_eq!(
+            result_id("hash", &json!({ "spendable": 10, "cached": false })),
+            result_id("hash", &json!({ "spendable": 10, "cached": true }))
+        );
+    }
+
+    #[test]
+    fn test_format_timestamp() {
+        assert_eq!(format_timestamp(0), "1970-01-01 00:00:00");
+        assert_eq!(format_timestamp(951782400), "2000-02-29 00:00:00");
+        assert_eq!(format_timestamp(1700000000), "2023-11-14 22:13:20");
+    }
+
+    #[test]
+    fn test_render_escapes() {
+        let record = VerificationRecord {
+            id: 1,
+            request_hash: "hash".to_string(),
+            proof_psbt: None,
+            message: "<script>alert(1)</script> {{report}}".to_string(),
+            addresses: vec!["addr".to_string()],
+            anchor_height: Some(100),
+            anchor_hash: None,
+            outcome: "verified".to_string(),
+            report: json!({ "spendable": 10, "utxos": [] }),
+            backend: "ssl://electrum".to_string(),
+            created_at: 0,
+        };
+        let html = render("id", &record);
+        assert!(html.contains("Spendable: 10 sats"));
+        assert!(html.contains("&lt;script&gt;alert(1)&lt;/script&gt;"));
+        assert!(!html.contains("<script>alert(1)</script>"));
+        assert!(html.contains("{{report}}"));
+    }
+}
@@ -0,0 +1,109 @@
+//! Token bucket rate limiting per client, weighted by the work a request causes.
+
+use std::{
+    collections::HashMap,
+    sync::Mutex,
+    time::{Duration, Instant},
+};
+
+/// Clients tracked at most, the full buckets and then the least recently used are dropped.
+const MAX_CLIENTS: usize = 10_000;
+
+pub struct RateLimiter {
+    /// The most work a client can cause at once.
+    burst: f64,
+    /// Work units refilled per second.
+    rate: f64,
+    buckets: Mutex<HashMap<String, Bucket>>,
+}
+
+struct Bucket {
+    tokens: f64,
+    updated: Instant,
+}
+
+impl RateLimiter {
+    pub fn new(burst: u32, per_minute: u32) -> Self {
+        RateLimiter {
+            burst: burst as f64,
+            rate: per_minute as f64 / 60.0,
+            buckets: Mutex::new(HashMap::new()),
+        }
+    }
+
+    /// Take `cost` tokens from the bucket of the client, or return how long to wait for them.
+    /// Requests costing more than the burst take the whole bucket.
+    pub fn check(&self, client: &str, cost: u32, now: Instant) -> Result<(), Duration> {
+        let cost = (cost as f64).min(self.burst);
+        let mut buckets = self.buckets.lock().unwrap();
+        if buckets.len() >= MAX_CLIENTS && !buckets.contains_key(client) {
+            buckets.retain(|_, bucket| self.refill(bucket, now) < self.burst);
+            // clients rotating keys or addresses drain every bucket they get
+            if buckets.len() >= MAX_CLIENTS {
+                let oldest = buckets
+                    .iter()
+                    .min_by_key(|(_, bucket)| bucket.updated)
+                    .map(|(client, _)| client.clone());
+                if let Some(oldest) = oldest {
+                    buckets.remove(&oldest);
+                }
+            }
+        }
+        let bucket = buckets.entry(client.to_string()).or_insert(Bucket {
+            tokens: self.burst,
+            updated: now,
+        });
+        bucket.tokens = self.refill(bucket, now);
+        bucket.updated = now;
+        if bucket.tokens >= cost {
+            bucket.tokens -= cost;
+            Ok(())
+        } else {
+            Err(Duration::from_secs_f64((cost - bucket.tokens) / self.rate))
+        }
+    }
+
+    fn refill(&self, bucket: &Bucket, now: Instant) -> f64 {
+        let elapsed = now.saturating_duration_since(bucket.updated).as_secs_f64();
+        (bucket.tokens + elapsed * self.rate).min(self.burst)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_rate_limiter() {
+        let limiter = RateLimiter::new(10, 60);
+        let now = Instant::now();
+        assert_eq!(limiter.check("ip", 6, now), Ok(()));
+        assert_eq!(limiter.check("ip", 6, now), Err(Duration::from_secs(2)));
+        // other clients have their own bucket
+        assert_eq!(limiter.check("key", 6, now), Ok(()));
+
+        let later = now + Duration::from_secs(2);
+        assert_eq!(limiter.check("ip", 6, later), Ok(()));
+        // expensive requests take the whole bucket once it is full
+        let full = later + Duration::from_secs(10);
+        assert_eq!(limiter.check("ip", 50, full), Ok(()));
+        assert_eq!(limiter.check("ip", 1, full), Err(Duration::from_secs(1)));
+    }
+
+    #[test]
+    fn test_rate_limiter_bound() {
+        let limiter = RateLimiter::new(10, 60);
+        let now = Instant::now();
+        for client in 0..MAX_CLIENTS {
+            let at = now + Duration::from_millis(client as u64);
+            assert_eq!(limiter.check(&client.to_string(), 10, at), Ok(()));
+        }
+        // every bucket is drained, the least recently used one makes room
+        let later = now + Duration::from_secs(5);
+        assert_eq!(limiter.check("new", 10, later), Ok(()));
+        let buckets = limiter.buckets.lock().unwrap();
+        assert_eq!(buckets.len(), MAX_CLIENTS);
+        assert!(!buckets.contains_key("0"));
+        assert!(buckets.contains_key("1"));
+    }
+}
@@ -0,0 +1,1575 @@
+//! The HTTP API over the verifier, with the history, the attestations, the transparency log,
+//! the jobs, the monitors and the webhooks around it.
+
+use crate::{
+    attestation::Attestor,
+    auth::{self, ApiKeys},
+    cache::Cache,
+    coalesce::InFlight,
+    config::Config,
+    construction::{self, UnsignedProofRequest},
+    diff, health,
+    jobs::{self, Jobs},
+    liabilities::{InclusionProof, SumNode},
+    logging,
+    metrics::{error_code, metrics},
+    monitor, permalink,
+    ratelimit::RateLimiter,
+    storage::{self, NewVerification, Storage, VerificationQuery},
+    tls,
+    transparency::{self, Tree},
+    webhooks::{self, Webhooks},
+    ClaimedAmount, Progress, ProofOfReserves, ReportingBlock, Reserves, ReservesVerifier,
+};
+use actix_web::{get, middleware, rt, web, App, HttpRequest, HttpResponse, HttpServer, Responder};
+use bdk_wallet::bitcoin::{
+    base64::{engine::general_purpose::STANDARD, Engine as _},
+    hashes::{sha256, Hash},
+    psbt::Psbt,
+};
+use serde::{Deserialize, Serialize};
+use serde_json::json;
+use std::{
+    fs, io,
+    str::FromStr,
+    sync::{Arc, Mutex},
+    time::Instant,
+};
+use tokio::sync::mpsc;
+use tokio_stream::{wrappers::UnboundedReceiverStream, StreamExt};
+use tracing::{error, info, Span};
+
+#[derive(Debug, Serialize, Deserialize)]
+struct ProofOfLiabilities {
+    /// The published root of the Merkle-sum tree of customer balances.
+    root: SumNode,
+    /// The block height of the liabilities snapshot, the requested confirmations below the tip by
+    /// default.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    block_height: Option<usize>,
+    #[serde(default)]
+    inclusion_proofs: Vec<InclusionProof>,
+    reserves: ProofOfReserves,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+struct InclusionCheck {
+    /// The published root of the Merkle-sum tree of customer balances.
+    root: SumNode,
+    #[serde(flatten)]
+    proof: InclusionProof,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+struct AttestationCheck {
+    /// A compact JWS as returned in the `attestation` field of a verification result.
+    attestation: String,
+}
+
+#[derive(Debug, Deserialize)]
+struct LogEntriesQuery {
+    #[serde(default)]
+    start: i64,
+    end: Option<i64>,
+}
+
+#[derive(Debug, Deserialize)]
+struct LogInclusionQuery {
+    index: usize,
+    /// The size of the tree to prove inclusion in, the current size by default.
+    tree_size: Option<usize>,
+}
+
+#[derive(Debug, Deserialize)]
+struct LogConsistencyQuery {
+    first: usize,
+    second: usize,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+struct MonitorRegistration {
+    addresses: Vec<String>,
+    message: String,
+    proof_psbt: String,
+    /// Alert when the spendable amount falls below this amount.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    floor: Option<ClaimedAmount>,
+    /// Seconds between checks, on every new block by default.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    interval: Option<u64>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+struct WebhookRegistration {
+    url: String,
+    /// The key of the HMAC signature in the `X-Webhook-Signature` header.
+    secret: String,
+    /// The events to deliver, all of them by default.
+    #[serde(default)]
+    events: Vec<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+struct ProofDiffRequest {
+    before: DiffInput,
+    after: DiffInput,
+}
+
+/// One side of a diff, either a proof to verify or a verification of the history.
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(untagged)]
+enum DiffInput {
+    Proof(ProofOfReserves),
+    /// Results are loaded from the history rather than taken from the client, which could pass
+    /// anything off as verified.
+    Verification {
+        verification_id: i64,
+    },
+}
+
+/// Serve the API with the given configuration until the server is stopped.
+pub async fn run(config: Config) -> io::Result<()> {
+    let tls_config = match (&config.tls.cert_file, &config.tls.key_file) {
+        (Some(cert_path), Some(key_path)) => {
+            let cert =
+                Arc::new(tls::ReloadableCert::load(cert_path, key_path).map_err(io::Error::other)?);
+            if let Some(path) = &config.tls.client_ca_file {
+                info!("Requiring client certificates signed by {}.", path);
+            }
+            tls::reload_on_sighup(cert.clone())?;
+            Some(
+                tls::server_config(cert, config.tls.client_ca_file.as_deref())
+                    .map_err(io::Error::other)?,
+            )
+        }
+        _ => None,
+    };
+
+    match tls_config {
+        Some(_) => info!("Starting HTTPS server at https://{}.", config.bind_address),
+        None => info!("Starting HTTP server at http://{}.", config.bind_address),
+    }
+    info!("Run with --help for the configuration file, the flags and their env vars.");
+
+    let database_path = config.storage.database_path.as_deref();
+    match database_path {
+        Some(path) => info!("Storing verifications in {}.", path),
+        None => info!(
+            "Storing the latest verifications in memory, set a database path to keep all of them."
+        ),
+    }
+    let storage = web::Data::new(Storage::open(database_path).map_err(io::Error::other)?);
+    let log = web::Data::new(Mutex::new(Tree::default()));
+
+    let attestation_seed = match (&config.attestation.key, &config.attestation.key_file) {
+        (Some(seed), _) => Some(seed.clone()),
+        (None, Some(path)) => Some(fs::read_to_string(path)?),
+        (None, None) => None,
+    };
+    let attestor = attestation_seed
+        .map(|seed| Attestor::from_hex(&seed))
+        .transpose()
+        .map_err(io::Error::other)?
+        .map(web::Data::new);
+    if let Some(attestor) = &attestor {
+        info!(
+            "Signing verification results with attestation key {}.",
+            attestor.key_id()
+        );
+    }
+
+    let api_keys = load_api_keys(&config)
+        .map_err(io::Error::other)?
+        .map(|api_keys| {
+            info!("Requiring one of {} API keys.", api_keys.len());
+            web::Data::new(api_keys)
+        });
+
+    let config = web::Data::new(config);
+    let features = &config.features;
+    let cache = features.cache.then(|| web::Data::new(Cache::default()));
+    let verifier = web::Data::new(
+        ReservesVerifier::from_config(
+            &config,
+            cache.as_ref().map(|cache| cache.clone().into_inner()),
+        )
+        .map_err(io::Error::other)?,
+    );
+    let webhooks = features
+        .webhooks
+        .then(|| web::Data::new(Webhooks::new(storage.clone().into_inner())));
+    if features.monitors {
+        monitor::spawn(
+            storage.clone().into_inner(),
+            webhooks
+                .as_deref()
+                .map(|webhooks| Webhooks::clone(webhooks)),
+            verifier.clone().into_inner(),
+        );
+    }
+    let in_flight = features
+        .coalescing
+        .then(|| web::Data::new(InFlight::default()));
+
+    let rate_limit = &config.rate_limit;
+    let rate_limiter = (rate_limit.per_minute > 0).then(|| {
+        info!(
+            "Limiting verifications to {} units of work per minute and client, bursts of {}.",
+            rate_limit.per_minute, rate_limit.burst
+        );
+        web::Data::new(RateLimiter::new(rate_limit.burst, rate_limit.per_minute))
+    });
+    let jobs = if features.jobs {
+        info!(
+            "Running verification jobs on {} workers.",
+            config.jobs.workers
+        );
+        let verify: Arc<jobs::Verifier> = {
+            let storage = storage.clone();
+            let attestor = attestor.clone();
+            let webhooks = webhooks.clone();
+            let verifier = verifier.clone();
+            Arc::new(move |proof, on_progress| {
+                verify_and_record(
+                    proof,
+                    &storage,
+                    attestor.as_deref().map(Arc::as_ref),
+                    webhooks.as_deref().map(Arc::as_ref),
+                    &verifier,
+                    on_progress,
+                )
+            })
+        };
+        Some(web::Data::new(
+            Jobs::start(storage.clone().into_inner(), config.jobs.workers, verify)
+                .map_err(io::Error::other)?,
+        ))
+    } else {
+        None
+    };
+
+    let bind_address = config.bind_address.clone();
+    let server = HttpServer::new(move || {
+        App::new()
+            .wrap(middleware::from_fn(auth::middleware))
+            .wrap(middleware::from_fn(logging::middleware))
+            .app_data(storage.clone())
+            .app_data(log.clone())
+            .app_data(config.clone())
+            .app_data(verifier.clone())
+            .configure(|cfg| {
+                if let Some(attestor) = &attestor {
+                    cfg.app_data(attestor.clone());
+                }
+                if let Some(rate_limiter) = &rate_limiter {
+                    cfg.app_data(rate_limiter.clone());
+                }
+                if let Some(api_keys) = &api_keys {
+                    cfg.app_data(api_keys.clone());
+                }
+                if let Some(cache) = &cache {
+                    cfg.app_data(cache.clone());
+                }
+                if let Some(in_flight) = &in_flight {
+                    cfg.app_data(in_flight.clone());
+                }
+                if config.features.metrics {
+                    cfg.service(web::resource("/metrics").route(web::get().to(show_metrics)));
+                }
+                if config.features.streaming {
+                    cfg.service(web::resource("/proof/stream").route(web::post().to(stream_proof)));
+                }
+                if config.features.proof_construction {
+                    cfg.service(
+                        web::resource("/proof/unsigned").route(web::post().to(unsigned_proof)),
+                    );
+                }
+                if let Some(jobs) = &jobs {
+                    cfg.app_data(jobs.clone())
+                        .service(web::resource("/jobs").route(web::post().to(submit_job)))
+                        .service(web::resource("/jobs/{id}").route(web::get().to(get_job)));
+                }
+                if config.features.monitors {
+                    cfg.service(
+                        web::resource("/monitors")
+                            .route(web::get().to(list_monitors))
+                            .route(web::post().to(create_monitor)),
+                    )
+                    .service(
+                        web::resource("/monitors/{id}")
+                            .route(web::get().to(get_monitor))
+                            .route(web::delete().to(delete_monitor)),
+                    )
+                    .service(
+                        web::resource("/monitors/{id}/events").route(web::get().to(monitor_events)),
+                    );
+                }
+                if let Some(webhooks) = &webhooks {
+                    cfg.app_data(webhooks.clone())
+                        .service(
+                            web::resource("/webhooks")
+                                .route(web::get().to(list_webhooks))
+                                .route(web::post().to(create_webhook)),
+                        )
+                        .service(
+                            web::resource("/webhooks/{id}").route(web::delete().to(delete_webhook)),
+                        )
+                        .service(
+                            web::resource("/webhooks/{id}/deliveries")
+                                .route(web::get().to(webhook_deliveries)),
+                        );
+                }
+            })
+            .app_data(web::JsonConfig::default().limit(config.payload_limit)) // <- limit size of the payload (global configuration)
+            .service(web::resource("/proof").route(web::post().to(check_proof)))
+            .service(web::resource("/liabilities").route(web::post().to(check_liabilities)))
+            .service(
+                web::resource("/inclusion")
+                    .route(web::get().to(inclusion))
+                    .route(web::post().to(check_inclusion)),
+            )
+            .service(web::resource("/diff").route(web::post().to(diff_proofs)))
+            .service(web::resource("/verifications").route(web::get().to(list_verifications)))
+            .service(web::resource("/verifications/{id}").route(web::get().to(get_verification)))
+            .service(web::resource("/v/{id}").route(web::get().to(show_permalink)))
+            .service(web::resource("/attestation/key").route(web::get().to(attestation_key)))
+            .service(web::resource("/attestation/verify").route(web::post().to(verify_attestation)))
+            .service(web::resource("/log/sth").route(web::get().to(log_tree_head)))
+            .service(web::resource("/log/entries").route(web::get().to(log_entries)))
+            .service(web::resource("/log/inclusion").route(web::get().to(log_inclusion)))
+            .service(web::resource("/log/consistency").route(web::get().to(log_consistency)))
+            .service(web::resource("/healthz").route(web::get().to(healthz)))
+            .service(web::resource("/readyz").route(web::get().to(readyz)))
+            .service(web::resource("/admin/cache/flush").route(web::post().to(flush_cache)))
+            .service(web::resource("/admin/keys").route(web::get().to(list_api_keys)))
+            .service(index)
+    });
+    match tls_config {
+        Some(tls_config) => server.bind_rustls_0_23(bind_address, tls_config)?,
+        None => server.bind(bind_address)?,
+    }
+    .run()
+    .await
+}
+
+#[get("/")]
+async fn index() -> impl Responder {
+    let html = include_str!("../res/index.html");
+    HttpResponse::Ok().content_type("text/html").body(html)
+}
+
+async fn inclusion() -> impl Responder {
+    let html = include_str!("../res/inclusion.html");
+    HttpResponse::Ok().content_type("text/html").body(html)
+}
+
+/// Liveness: the process answers.
+async fn healthz() -> HttpResponse {
+    HttpResponse::Ok().content_type("text/json").body(
+        json!({
+            "status": "alive",
+            "version": env!("CARGO_PKG_VERSION"),
+            "time": storage::now(),
+        })
+        .to_string(),
+    )
+}
+
+/// Readiness: the storage answers and so does the backend of every network, with a recent tip.
+async fn readyz(req: HttpRequest, storage: web::Data<Storage>) -> HttpResponse {
+    let max_tip_age = request_config(&req).health.max_tip_age;
+    let now = storage::now();
+    let probes = request_verifier(&req)
+        .backends()
+        .iter()
+        .map(|(network, backend)| {
+            let (network, backend) = (*network, backend.clone());
+            rt::task::spawn_blocking(move || {
+                health::probe(network, backend.as_ref(), max_tip_age, now)
+            })
+        })
+        .collect::<Vec<_>>();
+    let mut backends = Vec::new();
+    for probe in probes {
+        match probe.await {
+            Ok(status) => backends.push(json!(status)),
+            Err(e) => backends.push(json!({ "ready": false, "error": format!("{:?}", e) })),
+        }
+    }
+    let storage = match storage.count_jobs() {
+        Ok(_) => json!({ "ready": true }),
+        Err(e) => json!({ "ready": false, "error": e }),
+    };
+    let ready = storage["ready"] == true && backends.iter().all(|backend| backend["ready"] == true);
+
+    let mut response = if ready {
+        HttpResponse::Ok()
+    } else {
+        HttpResponse::ServiceUnavailable()
+    };
+    response
+        .content_type("text/json")
+        .body(json!({ "ready": ready, "storage": storage, "backends": backends }).to_string())
+}
+
+async fn check_proof(
+    item: web::Json<ProofOfReserves>,
+    req: HttpRequest,
+    storage: web::Data<Storage>,
+    attestor: Option<web::Data<Attestor>>,
+    webhooks: Option<web::Data<Webhooks>>,
+    in_flight: Option<web::Data<InFlight>>,
+) -> HttpResponse {
+    if let Some(response) = rate_limited(&req, request_cost(&item)) {
+        return response;
+    }
+
+    let item = item.into_inner();
+    let key = request_hash(&item);
+    let verifier = request_verifier(&req);
+    let verify = move || {
+        verify_and_record(
+            &item,
+            &storage,
+            attestor.as_deref().map(Arc::as_ref),
+            webhooks.as_deref().map(Arc::as_ref),
+            &verifier,
+            &mut |_| {},
+        )
+    };
+    let answer = match in_flight {
+        Some(in_flight) => in_flight
+            .run(key, verify)
+            .await
+            .map(|(answer, _)| answer)
+            .unwrap_or_else(|e| json!({ "error": e })),
+        None => {
+            // off the worker thread, which would stall every other request meanwhile
+            let span = Span::current();
+            web::block(move || span.in_scope(verify))
+                .await
+                .unwrap_or_else(|e| json!({ "error": format!("{:?}", e) }))
+        }
+    };
+
+    HttpResponse::Ok()
+        .content_type("text/json")
+        .body(answer.to_string())
+}
+
+/// Verify a proof like `/proof`, streaming the progress as server-sent events before the result.
+async fn stream_proof(
+    item: web::Json<ProofOfReserves>,
+    req: HttpRequest,
+    storage: web::Data<Storage>,
+    attestor: Option<web::Data<Attestor>>,
+    webhooks: Option<web::Data<Webhooks>>,
+) -> HttpResponse {
+    if let Some(response) = rate_limited(&req, request_cost(&item)) {
+        return response;
+    }
+    let (sender, receiver) = mpsc::unbounded_channel();
+    let item = item.into_inner();
+    let verifier = request_verifier(&req);
+    let span = Span::current();
+    rt::task::spawn_blocking(move || {
+        let _span = span.enter();
+        let answer = verify_and_record(
+            &item,
+            &storage,
+            attestor.as_deref().map(Arc::as_ref),
+            webhooks.as_deref().map(Arc::as_ref),
+            &verifier,
+            &mut |progress| {
+                // the client may have gone away, the verification is recorded anyway
+                let _ = sender.send(sse_event("progress", &json!(progress)));
+            },
+        );
+        let _ = sender.send(sse_event("result", &answer));
+    });
+
+    HttpResponse::Ok()
+        .content_type("text/event-stream")
+        .insert_header(("Cache-Control", "no-cache"))
+        .streaming(UnboundedReceiverStream::new(receiver).map(Ok::<_, actix_web::Error>))
+}
+
+/// A server-sent event with JSON data.
+fn sse_event(event: &str, data: &serde_json::Value) -> web::Bytes {
+    web::Bytes::from(format!("event: {}\ndata: {}\n\n", event, data))
+}
+
+/// Verify a proof of reserves and record the result in the history, the permalinks and the
+/// transparency log, attest it and notify the webhooks, returning the answer of `/proof`.
+fn verify_and_record(
+    item: &ProofOfReserves,
+    storage: &Storage,
+    attestor: Option<&Attestor>,
+    webhooks: Option<&Webhooks>,
+    verifier: &ReservesVerifier,
+    on_progress: &mut dyn FnMut(Progress),
+) -> serde_json::Value {
+    info!(
+        addresses = ?logging::redact_all(&item.addresses),
+        proof_psbt = %logging::redact(&item.proof_psbt),
+        "Verifying proof"
+    );
+    let started = Instant::now();
+    let answer = match verifier.verify_with_progress(item, on_progress) {
+        Err(e) => json!({ "error": e }),
+        Ok(report) => json!(report),
+    };
+    metrics().record_verification(&answer, started.elapsed());
+    info!(
+        error = answer
+            .get("error")
+            .and_then(|error| error.as_str())
+            .map(error_code),
+        duration_ms = started.elapsed().as_millis() as u64,
+        "Verified proof"
+    );
+
+    let backend = verifier
+        .backend_for(&item.addresses)
+        .map(|(backend, _)| backend.name())
+        .unwrap_or_default();
+    let request_hash = request_hash(item);
+    let verification = NewVerification {
+        request_hash: &request_hash,
+        proof_psbt: &item.proof_psbt,
+        message: &item.message,
+        addresses: &item.addresses,
+        backend,
+        report: &answer,
+    };
+    let permalink = storage
+        .insert_verification(&verification)
+        .and_then(|verification_id| {
+            let id = permalink::result_id(&request_hash, &answer);
+            storage.insert_permalink(&id, verification_id)?;
+            Ok(format!("/v/{}", id))
+        });
+    let mut answer = answer;
+    match permalink {
+        Ok(permalink) => answer["permalink"] = json!(permalink),
+        Err(e) => error!("{}", e),
+    }
+    let log_entry = json!({ "request_hash": request_hash, "report": answer }).to_string();
+    let leaf_hash = transparency::leaf_hash(log_entry.as_bytes()).to_string();
+    match storage.append_log_entry(&leaf_hash, &log_entry) {
+        Ok(log_index) => answer["log_index"] = json!(log_index),
+        Err(e) => error!("{}", e),
+    }
+    if let Some(attestor) = attestor {
+        answer["attestation"] = json!(attestor.sign(&json!({
+            "iat": storage::now(),
+            "request_hash": request_hash,
+            "message": item.message,
+            "report": answer,
+        })));
+    }
+    if let Some(webhooks) = webhooks {
+        let event = if answer.get("error").is_some() {
+            "verification_failed"
+        } else {
+            "verification_completed"
+        };
+        webhooks.notify(
+            event,
+            json!({ "request_hash": request_hash, "report": answer }),
+        );
+    }
+
+    answer
+}
+
+/// Build the unsigned proof of a watch-only wallet, for the custodian to sign offline.
+async fn unsigned_proof(item: web::Json<UnsignedProofRequest>, req: HttpRequest) -> HttpResponse {
+    // every scanned script costs like an address of a proof
+    let descriptors = 1 + item.change_descriptor.is_some() as usize;
+    let cost = item
+        .stop_gap()
+        .saturating_mul(descriptors)
+        .saturating_add(1);
+    if let Some(response) = rate_limited(&req, u32::try_from(cost).unwrap_or(u32::MAX)) {
+        return response;
+    }
+    let item = item.into_inner();
+    let verifier = request_verifier(&req);
+    let result = web::block(move || construction::unsigned_proof(&item, &verifier))
+        .await
+        .map_err(|e| format!("{:?}", e))
+        .and_then(|result| result);
+    json_answer(result.map(|proof| json!(proof)))
+}
+
+async fn submit_job(
+    item: web::Json<ProofOfReserves>,
+    req: HttpRequest,
+    jobs: web::Data<Jobs>,
+) -> HttpResponse {
+    if let Some(response) = rate_limited(&req, request_cost(&item)) {
+        return response;
+    }
+    json_answer(
+        jobs.submit(&item)
+            .map(|id| json!({ "id": id, "status": "queued" })),
+    )
+}
+
+async fn get_job(id: web::Path<i64>, storage: web::Data<Storage>) -> HttpResponse {
+    match storage.get_job(*id) {
+        Ok(None) => HttpResponse::NotFound()
+            .content_type("text/json")
+            .body(json!({ "error": "Job not found" }).to_string()),
+        result => json_answer(result.map(|job| json!(job))),
+    }
+}
+
+async fn attestation_key(attestor: Option<web::Data<Attestor>>) -> HttpResponse {
+    match attestor {
+        Some(attestor) => HttpResponse::Ok()
+            .content_type("text/json")
+            .body(attestor.jwk().to_string()),
+        None => attestations_disabled(),
+    }
+}
+
+async fn verify_attestation(
+    item: web::Json<AttestationCheck>,
+    attestor: Option<web::Data<Attestor>>,
+) -> HttpResponse {
+    let Some(attestor) = attestor else {
+        return attestations_disabled();
+    };
+    let answer = match attestor.verify(&item.attestation) {
+        Ok(payload) => json!({ "valid": true, "payload": payload }),
+        Err(e) => json!({ "valid": false, "error": e }),
+    }
+    .to_string();
+    HttpResponse::Ok().content_type("text/json").body(answer)
+}
+
+fn attestations_disabled() -> HttpResponse {
+    HttpResponse::NotFound()
+        .content_type("text/json")
+        .body(json!({ "error": "Attestations are not enabled on this server" }).to_string())
+}
+
+/// The signed tree head, which needs the attestation key: unsigned, the server could show every
+/// monitor a different log.
+async fn log_tree_head(
+    storage: web::Data<Storage>,
+    log: web::Data<Mutex<Tree>>,
+    attestor: Option<web::Data<Attestor>>,
+) -> HttpResponse {
+    let Some(attestor) = attestor else {
+        return attestations_disabled();
+    };
+    with_log(storage, log, move |tree| {
+        let tree_head = json!({
+            "tree_size": tree.len(),
+            "root_hash": tree.root(tree.len()),
+            "timestamp": storage::now(),
+        });
+        let mut signed_tree_head = tree_head.clone();
+        signed_tree_head["signature"] = json!(attestor.sign(&tree_head));
+        Ok(signed_tree_head)
+    })
+    .await
+}
+
+async fn log_entries(
+    query: web::Query<LogEntriesQuery>,
+    storage: web::Data<Storage>,
+) -> HttpResponse {
+    let start = query.start;
+    if start < 0 {
+        return json_answer(Err(format!("Invalid start {}", start)));
+    }
+    let end = query
+        .end
+        .unwrap_or(start.saturating_add(100))
+        .min(start.saturating_add(1000));
+    let result = web::block(move || storage.log_entries(start, end))
+        .await
+        .map_err(|e| format!("{:?}", e))
+        .and_then(|result| result);
+    json_answer(result.map(|entries| json!(entries)))
+}
+
+async fn log_inclusion(
+    query: web::Query<LogInclusionQuery>,
+    storage: web::Data<Storage>,
+    log: web::Data<Mutex<Tree>>,
+) -> HttpResponse {
+    let query = query.into_inner();
+    with_log(storage, log, move |tree| {
+        let tree_size = query.tree_size.unwrap_or(tree.len());
+        if tree_size > tree.len() || query.index >= tree_size {
+            return Err(format!(
+                "Leaf {} is not part of a tree of size {}",
+                query.index, tree_size
+            ));
+        }
+        Ok(json!({
+            "leaf_index": query.index,
+            "leaf_hash": tree.leaf(query.index),
+            "tree_size": tree_size,
+            "root_hash": tree.root(tree_size),
+            "audit_path": tree.inclusion_proof(query.index, tree_size),
+        }))
+    })
+    .await
+}
+
+async fn log_consistency(
+    query: web::Query<LogConsistencyQuery>,
+    storage: web::Data<Storage>,
+    log: web::Data<Mutex<Tree>>,
+) -> HttpResponse {
+    let query = query.into_inner();
+    with_log(storage, log, move |tree| {
+        if query.first == 0 || query.first > query.second || query.second > tree.len() {
+            return Err(format!(
+                "Invalid tree sizes {} and {}",
+                query.first, query.second
+            ));
+        }
+        Ok(json!({
+            "first": query.first,
+            "second": query.second,
+            "first_root_hash": tree.root(query.first),
+            "second_root_hash": tree.root(query.second),
+            "consistency": tree.consistency_proof(query.first, query.second),
+        }))
+    })
+    .await
+}
+
+/// Answer from the tree of the transparency log on a blocking thread, after adding the entries
+/// appended since the last request to it.
+async fn with_log(
+    storage: web::Data<Storage>,
+    log: web::Data<Mutex<Tree>>,
+    answer: impl FnOnce(&Tree) -> Result<serde_json::Value, String> + Send + 'static,
+) -> HttpResponse {
+    let result = web::block(move || {
+        let mut tree = log.lock().unwrap();
+        for leaf_hash in storage.log_leaf_hashes(tree.len() as i64)? {
+            let leaf_hash = sha256::Hash::from_str(&leaf_hash)
+                .map_err(|e| format!("Invalid leaf hash: {:?}", e))?;
+            tree.push(leaf_hash);
+        }
+        answer(&tree)
+    })
+    .await
+    .map_err(|e| format!("{:?}", e))
+    .and_then(|result| result);
+    json_answer(result)
+}
+
+async fn create_monitor(
+    item: web::Json<MonitorRegistration>,
+    req: HttpRequest,
+    storage: web::Data<Storage>,
+) -> HttpResponse {
+    json_answer(
+        item.floor
+            .as_ref()
+            .map(ClaimedAmount::to_sat)
+            .transpose()
+            .and_then(|floor| {
+                if item.addresses.is_empty() {
+                    return Err("No address provided".to_string());
+                }
+                request_verifier(&req)
+                    .limits()
+                    .check_addresses(item.addresses.len())?;
+                storage.insert_monitor(
+                    &item.proof_psbt,
+                    &item.message,
+                    &item.addresses,
+                    floor,
+                    item.interval,
+                )
+            })
+            .map(|id| json!({ "id": id })),
+    )
+}
+
+async fn list_monitors(storage: web::Data<Storage>) -> HttpResponse {
+    json_answer(storage.list_monitors().map(|monitors| json!(monitors)))
+}
+
+async fn get_monitor(id: web::Path<i64>, storage: web::Data<Storage>) -> HttpResponse {
+    match storage.get_monitor(*id) {
+        Ok(None) => monitor_not_found(),
+        result => json_answer(result.map(|monitor| json!(monitor))),
+    }
+}
+
+async fn delete_monitor(id: web::Path<i64>, storage: web::Data<Storage>) -> HttpResponse {
+    match storage.delete_monitor(*id) {
+        Ok(false) => monitor_not_found(),
+        result => json_answer(result.map(|deleted| json!({ "deleted": deleted }))),
+    }
+}
+
+async fn monitor_events(id: web::Path<i64>, storage: web::Data<Storage>) -> HttpResponse {
+    match storage.get_monitor(*id) {
+        Ok(None) => monitor_not_found(),
+        Ok(Some(_)) => json_answer(storage.list_monitor_events(*id).map(|events| json!(events))),
+        Err(e) => json_answer(Err(e)),
+    }
+}
+
+fn monitor_not_found() -> HttpResponse {
+    HttpResponse::NotFound()
+        .content_type("text/json")
+        .body(json!({ "error": "Monitor not found" }).to_string())
+}
+
+async fn create_webhook(
+    item: web::Json<WebhookRegistration>,
+    storage: web::Data<Storage>,
+) -> HttpResponse {
+    let url = item.url.clone();
+    // resolving the host blocks
+    if let Err(e) = web::block(move || webhooks::check_url(&url))
+        .await
+        .map_err(|e| format!("{:?}", e))
+        .and_then(|result| result)
+    {
+        return json_answer(Err(e));
+    }
+    if let Some(event) = item
+        .events
+        .iter()
+        .find(|event| !webhooks::EVENTS.contains(&event.as_str()))
+    {
+        return json_answer(Err(format!(
+            "Unknown event {}, expected one of {}",
+            event,
+            webhooks::EVENTS.join(", ")
+        )));
+    }
+    json_answer(
+        storage
+            .insert_webhook(&item.url, &item.secret, &item.events)
+            .map(|id| json!({ "id": id })),
+    )
+}
+
+async fn list_webhooks(storage: web::Data<Storage>) -> HttpResponse {
+    json_answer(storage.list_webhooks().map(|webhooks| json!(webhooks)))
+}
+
+async fn delete_webhook(id: web::Path<i64>, storage: web::Data<Storage>) -> HttpResponse {
+    match storage.delete_webhook(*id) {
+        Ok(false) => HttpResponse::NotFound()
+            .content_type("text/json")
+            .body(json!({ "error": "Webhook not found" }).to_string()),
+        result => json_answer(result.map(|deleted| json!({ "deleted": deleted }))),
+    }
+}
+
+async fn webhook_deliveries(id: web::Path<i64>, storage: web::Data<Storage>) -> HttpResponse {
+    json_answer(
+        storage
+            .list_webhook_deliveries(*id)
+            .map(|deliveries| json!(deliveries)),
+    )
+}
+
+async fn flush_cache(cache: Option<web::Data<Cache>>) -> HttpResponse {
+    let flushed = cache.map_or(0, |cache| cache.clear());
+    json_answer(Ok(json!({ "flushed": flushed })))
+}
+
+async fn show_metrics(storage: web::Data<Storage>) -> HttpResponse {
+    match storage
+        .count_jobs()
+        .and_then(|jobs| metrics().encode(&jobs))
+    {
+        Ok(body) => HttpResponse::Ok()
+            .content_type("application/openmetrics-text; version=1.0.0; charset=utf-8")
+            .body(body),
+        Err(e) => {
+            error!("{}", e);
+            HttpResponse::InternalServerError()
+                .content_type("text/plain")
+                .body("Failed to collect metrics")
+        }
+    }
+}
+
+async fn list_api_keys(api_keys: Option<web::Data<ApiKeys>>) -> HttpResponse {
+    let keys = api_keys
+        .as_deref()
+        .map(|keys| keys.list())
+        .unwrap_or_default();
+    json_answer(Ok(json!(keys)))
+}
+
+/// The leaf hashes of the first `tree_size` entries of the transparency log, or all of them.
+fn json_answer(result: Result<serde_json::Value, String>) -> HttpResponse {
+    let answer = match result {
+        Err(e) => json!({ "error": e }),
+        Ok(res) => res,
+    }
+    .to_string();
+    HttpResponse::Ok().content_type("text/json").body(answer)
+}
+
+async fn show_permalink(id: web::Path<String>, storage: web::Data<Storage>) -> HttpResponse {
+    match storage.get_permalink(&id) {
+        Ok(Some(record)) => HttpResponse::Ok()
+            .content_type("text/html")
+            .body(permalink::render(&id, &record)),
+        Ok(None) => HttpResponse::NotFound()
+            .content_type("text/html")
+            .body("Verification not found"),
+        Err(e) => {
+            error!("{}", e);
+            HttpResponse::InternalServerError()
+                .content_type("text/html")
+                .body("Failed to load verification")
+        }
+    }
+}
+
+/// Charge the work of a request to the client, by configured API key or else by IP address, and
+/// return the `429 Too Many Requests` answer when it is over its limit.
+fn rate_limited(req: &HttpRequest, cost: u32) -> Option<HttpResponse> {
+    let limiter = req.app_data::<web::Data<RateLimiter>>()?;
+    // unknown keys are not trusted, or a client could get a new bucket with every request
+    let key = req
+        .app_data::<web::Data<ApiKeys>>()
+        .and_then(|keys| keys.authenticate(req.headers()));
+    let client = match key {
+        Some(key) => format!("key:{}", key.sha256),
+        None => format!(
+            "ip:{}",
+            req.peer_addr()
+                .map(|addr| addr.ip().to_string())
+                .unwrap_or_default()
+        ),
+    };
+    limiter
+        .check(&client, cost, Instant::now())
+        .err()
+        .map(|retry_after| {
+            let seconds = retry_after.as_secs_f64().ceil() as u64;
+            HttpResponse::TooManyRequests()
+                .content_type("text/json")
+                .insert_header(("Retry-After", seconds.to_string()))
+                .body(
+                    json!({
+                        "error": format!("Rate limit exceeded, retry in {} seconds", seconds)
+                    })
+                    .to_string(),
+                )
+        })
+}
+
+/// The configuration of the server, or the defaults if there is none.
+fn request_config(req: &HttpRequest) -> Arc<Config> {
+    req.app_data::<web::Data<Config>>()
+        .map(|config| config.clone().into_inner())
+        .unwrap_or_default()
+}
+
+/// The verifier of the server, or one with the default configuration if there is none.
+/// The keys of the configuration and of its keys file, if there are any.
+fn load_api_keys(config: &Config) -> Result<Option<ApiKeys>, String> {
+    let mut keys = config.auth.keys.clone();
+    if let Some(path) = &config.auth.keys_file {
+        keys.extend(auth::load_keys(path)?);
+    }
+    // the validation only sees that there is a keys file, which may hold no keys
+    let features = &config.features;
+    if keys.is_empty() && (features.monitors || features.webhooks) {
+        return Err("Monitors and webhooks require API keys, but none were loaded".to_string());
+    }
+    Ok((!keys.is_empty()).then(|| ApiKeys::new(keys)))
+}
+
+fn request_verifier(req: &HttpRequest) -> Arc<ReservesVerifier> {
+    req.app_data::<web::Data<ReservesVerifier>>()
+        .map(|verifier| verifier.clone().into_inner())
+        .unwrap_or_else(|| {
+            let verifier = ReservesVerifier::from_config(&Config::default(), None);
+            Arc::new(verifier.expect("default backends"))
+        })
+}
+
+/// The work of verifying a proof: one unit per request, address and proof input.
+fn request_cost(proof: &ProofOfReserves) -> u32 {
+    let inputs = STANDARD
+        .decode(&proof.proof_psbt)
+        .ok()
+        .and_then(|psbt| Psbt::deserialize(&psbt).ok())
+        .map(|psbt| psbt.unsigned_tx.input.len())
+        .unwrap_or_default();
+    (1 + proof.addresses.len() + inputs) as u32
+}
+
+/// Hash of a request, identifying repeated submissions of the same proof.
+fn request_hash(proof: &ProofOfReserves) -> String {
+    let request = serde_json::to_vec(proof).expect("serializable request");
+    sha256::Hash::hash(&request).to_string()
+}
+
+async fn list_verifications(
+    query: web::Query<VerificationQuery>,
+    storage: web::Data<Storage>,
+) -> HttpResponse {
+    let answer = match storage.list_verifications(&query) {
+        Err(e) => json!({ "error": e }),
+        Ok(records) => json!(records),
+    }
+    .to_string();
+    HttpResponse::Ok().content_type("text/json").body(answer)
+}
+
+async fn get_verification(id: web::Path<i64>, storage: web::Data<Storage>) -> HttpResponse {
+    match storage.get_verification(*id) {
+        Err(e) => HttpResponse::Ok()
+            .content_type("text/json")
+            .body(json!({ "error": e }).to_string()),
+        Ok(None) => HttpResponse::NotFound()
+            .content_type("text/json")
+            .body(json!({ "error": "Verification not found" }).to_string()),
+        Ok(Some(record)) => HttpResponse::Ok()
+            .content_type("text/json")
+            .body(json!(record).to_string()),
+    }
+}
+
+async fn check_liabilities(item: web::Json<ProofOfLiabilities>, req: HttpRequest) -> HttpResponse {
+    if let Some(response) = rate_limited(&req, request_cost(&item.reserves)) {
+        return response;
+    }
+    let item = item.into_inner();
+    let verifier = request_verifier(&req);
+    json_answer(
+        web::block(move || handle_liabilities(&item, &verifier))
+            .await
+            .map_err(|e| format!("{:?}", e))
+            .and_then(|result| result),
+    )
+}
+
+fn handle_liabilities(
+    liabilities: &ProofOfLiabilities,
+    verifier: &ReservesVerifier,
+) -> Result<serde_json::Value, String> {
+    let inclusion_proofs = liabilities
+        .inclusion_proofs
+        .iter()
+        .map(|proof| match proof.verify(&liabilities.root) {
+            Ok(()) => json!({ "valid": true, "balance": proof.leaf.balance }),
+            Err(e) => json!({ "valid": false, "error": e }),
+        })
+        .collect::<Vec<_>>();
+
+    let reporting_block = match liabilities.block_height {
+        Some(height) => ReportingBlock::Height(height),
+        None => ReportingBlock::Confirmations(
+            verifier
+                .confirmations()
+                .resolve(liabilities.reserves.confirmations)?,
+        ),
+    };
+    let reserves = verifier.reserves(
+        &liabilities.reserves.message,
+        &liabilities.reserves.proof_psbt,
+        reporting_block,
+        &liabilities.reserves.addresses,
+        &mut |_| {},
+    )?;
+
+    let total_liabilities = liabilities.root.sum;
+    let ratio =
+        (total_liabilities > 0).then(|| reserves.spendable as f64 / total_liabilities as f64);
+
+    Ok(json!({
+        "spendable": reserves.spendable,
+        "total_liabilities": total_liabilities,
+        "ratio": ratio,
+        "block_height": reserves.block_height,
+        "inclusion_proofs": inclusion_proofs,
+    }))
+}
+
+async fn check_inclusion(item: web::Json<InclusionCheck>) -> HttpResponse {
+    let answer = match handle_inclusion(&item) {
+        Err(e) => json!({ "error": e }),
+        Ok(res) => res,
+    }
+    .to_string();
+    HttpResponse::Ok().content_type("text/json").body(answer)
+}
+
+fn handle_inclusion(check: &InclusionCheck) -> Result<serde_json::Value, String> {
+    let computed_root = check.proof.compute_root()?;
+    Ok(match check.proof.verify(&check.root) {
+        Ok(()) => json!({
+            "included": true,
+            "balance": check.proof.leaf.balance,
+            "computed_root": computed_root,
+        }),
+        Err(e) => json!({
+            "included": false,
+            "reason": e,
+            "computed_root": computed_root,
+        }),
+    })
+}
+
+async fn diff_proofs(
+    item: web::Json<ProofDiffRequest>,
+    req: HttpRequest,
+    storage: web::Data<Storage>,
+) -> HttpResponse {
+    // only the proofs are verified, the results were returned before
+    let cost = [&item.before, &item.after]
+        .into_iter()
+        .map(|input| match input {
+            DiffInput::Proof(proof) => request_cost(proof),
+            DiffInput::Verification { .. } => 0,
+        })
+        .fold(1, u32::saturating_add);
+    if let Some(response) = rate_limited(&req, cost) {
+        return response;
+    }
+    let item = item.into_inner();
+    let verifier = request_verifier(&req);
+    json_answer(
+        web::block(move || handle_diff(&item, &verifier, &storage))
+            .await
+            .map_err(|e| format!("{:?}", e))
+            .and_then(|result| result),
+    )
+}
+
+fn handle_diff(
+    request: &ProofDiffRequest,
+    verifier: &ReservesVerifier,
+    storage: &Storage,
+) -> Result<serde_json::Value, String> {
+    let resolve = |input: &DiffInput| match input {
+        DiffInput::Proof(proof) => verifier.reserves(
+            &proof.message,
+            &proof.proof_psbt,
+            ReportingBlock::Confirmations(verifier.confirmations().resolve(proof.confirmations)?),
+            &proof.addresses,
+            &mut |_| {},
+        ),
+        DiffInput::Verification { verification_id } => {
+            let record = storage
+                .get_verification(*verification_id)?
+                .ok_or_else(|| format!("Verification {} not found", verification_id))?;
+            if let Some(error) = record.report.get("error") {
+                return Err(format!(
+                    "Verification {} failed: {}",
+                    verification_id, error
+                ));
+            }
+            serde_json::from_value::<Reserves>(record.report)
+                .map_err(|e| format!("Invalid verification {}: {:?}", verification_id, e))
+        }
+    };
+    let before = resolve(&request.before).map_err(|e| format!("before: {}", e))?;
+    let after = resolve(&request.after).map_err(|e| format!("after: {}", e))?;
+
+    Ok(json!(diff::diff(&before, &after)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use actix_web::{body::to_bytes, dev::Service, http, test, web, App, Error};
+
+    #[actix_web::test]
+    async fn test_index() -> Result<(), Error> {
+        let app = App::new()
+            .app_data(web::Data::new(Storage::open(None).unwrap()))
+            .route("/proof", web::post().to(check_proof));
+        let app = test::init_service(app).await;
+
+        let req = test::TestRequest::post().uri("/proof")
+            .set_json(ProofOfReserves {
+                addresses: vec!["2Mtkk3kjyN8hgdGXPuJCNnwS3BBY4K2frhY".to_owned()],
+                message: "Stored in SEBA Bank AG cold storage".to_owned(),
+                claimed_amount: None,
+                confirmations: None,
+                proof_psbt: "cHNidP8BAP03AwEAAAATfUqjtTyZAxfGOsqFi93k3ToGtPZ0E/BZWFlBgAFlt1kAAAAAAP////8VZFle1kNhN87Ee3jTlpqzhPY3376Bee8gryZ4EP0QxQAAAAAA/////xdqWOlIfYFpbDM+ZuBHu05GiQz+EKK/ebafYy50BPwqAAAAAAD/////K6q1ppFH2Ai6FYgXhqAP/i25RVrCNl7/LKkDKAfBedkAAAAAAP////8rqrWmkUfYCLoViBeGoA/+LblFWsI2Xv8sqQMoB8F52QEAAAAA/////yvmR/yPrZNvLPEWPdteixrpIrSe+mjGV0PRHwQvJ3skAAAAAAD/////cuwKmKQFtYW/+/3y8/ePnheAut3yDHv0R7HV22UhJX0AAAAAAP////9y7AqYpAW1hb/7/fLz94+eF4C63fIMe/RHsdXbZSElfQEAAAAA/////4ygvq0AS059XinGKxwy8SqKjRANTF6dU+CDPXemeDqVAAAAAAD/////jKC+rQBLTn1eKcYrHDLxKoqNEA1MXp1T4IM9d6Z4OpUBAAAAAP////+3xGKCPa4t1MGlkJ9jznWYBGdP9XZNMKbW+t7UvnNxzAAAAAAA/////7fEYoI9ri3UwaWQn2POdZgEZ0/1dk0wptb63tS+c3HMAQAAAAD/////wyKNGqQJpgaNszr5mLLEYQV6+lAMfXNndS/mn8PkXJ0AAAAAAP/////DIo0apAmmBo2zOvmYssRhBXr6UAx9c2d1L+afw+RcnQEAAAAA/////9Fninwz/x77J2ghJX0wcVNLRI3f3wMIlh5kePz8l2ZuAAAAAAD/////1AUOKakFoN1BqrDomHASI0VFsLtskXVQpPljoDU8zWsBAAAAAP/////swob+WCNq5562PWB+Z5JOFFogd/20GAr4Vyra6oOIAAAAAAAA/////+zChv5YI2rnnrY9YH5nkk4UWiB3/bQYCvhXKtrqg4gAAQAAAAD/////8pkjhcQSFD62iDk1sC4WLBUPcpKNoeup0O98xe4MF+kAAAAAAP////8BbOw1AwAAAAAZdqkUn3/QltN+0sDj9/DPySS+70/862iIrAAAAAAAAQEKAAAAAAAAAAABUQEHAAABASAoOPwCAAAAABepFBCNSAfpaNUWLsnOLKCLqO4EAl4UhyICAyS3XurSwfnGDoretecAn+x6Ka/Nsw2CnYLQlWL+i66FSDBFAiEA6crnwxlLYnlcWc2LovFA7qbw017cI//bmND/tKSNuMkCIDMCDYT7WXeJ5BRJGZuA+MRNs6sWdxo2Yo47bkUPQCS5ASICA3Ro+OqZtsZHiDmLWtJUgMrQj0sNZb5UzjpV/SBrWuRySDBFAiEAreZ3cbl2oT7kEw7IDoU7ZF23rij0KFtuV4RqvkuXDuoCICueWRN9+sizOalX9N6tIr9hKe+W2Ib14K1QrjoGKhYVASICA/ctPZZmOw6pmwrrDX8nPKsRqN43iF8d3cjZESrbhxaTSDBFAiEAnBtH9h2MP0ket2WG17u+yY3i+dS+Udqejcbi50Y+7zICIAn38DAz3z/bPIr9gJnxBip8d5GwRxWe/zSsYrzDcM5YAQEEIgAgdBDiqcx7V0LtGgDr8Co4bneNqt4doLQVuq7q8EAvTw0BBfFTIQIvUztmfi6js24hlhyf6dyjQPvgr1IQFzqDrgM3qyCldiECa7U6mOgQvQ7mGg7RFkumwCR4bXZVTnk+IC3Gzpx4xOohAtW4p9ZqQf/bb0xT1hmUAi6Ia09FAB+xWLlckWTUX4yjIQMkt17q0sH5xg6K3rXnAJ/seimvzbMNgp2C0JVi/ouuhSEDLTT4kyIAgzSHvSlKohncvgALn5s9gkeZVBQwAJ8PpVEhA3Ro+OqZtsZHiDmLWtJUgMrQj0sNZb5UzjpV/SBrWuRyIQP3LT2WZjsOqZsK6w1/JzyrEajeN4hfHd3I2REq24cWk1euAQcjIgAgdBDiqcx7V0LtGgDr8Co4bneNqt4doLQVuq7q8EAvTw0BCP3PAQUASDBFAiEA6crnwxlLYnlcWc2LovFA7qbw017cI//bmND/tKSNuMkCIDMCDYT7WXeJ5BRJGZuA+MRNs6sWdxo2Yo47bkUPQCS5AUgwRQIhAK3md3G5dqE+5BMOyA6FO2Rdt64o9ChbbleEar5Llw7qAiArnlkTffrIszmpV/TerSK/YSnvltiG9eCtUK46BioWFQFIMEUCIQCcG0f2HYw/SR63ZYbXu77JjeL51L5R2p6NxuLnRj7vMgIgCffwMDPfP9s8iv2AmfEGKnx3kbBHFZ7/NKxivMNwzlgB8VMhAi9TO2Z+LqOzbiGWHJ/p3KNA++CvUhAXOoOuAzerIKV2IQJrtTqY6BC9DuYaDtEWS6bAJHhtdlVOeT4gLcbOnHjE6iEC1bin1mpB/9tvTFPWGZQCLohrT0UAH7FYuVyRZNRfjKMhAyS3XurSwfnGDoretecAn+x6Ka/Nsw2CnYLQlWL+i66FIQMtNPiTIgCDNIe9KUqiGdy+AAufmz2CR5lUFDAAnw+lUSEDdGj46pm2xkeIOYta0lSAytCPSw1lvlTOOlX9IGta5HIhA/ctPZZmOw6pmwrrDX8nPKsRqN43iF8d3cjZESrbhxaTV64AAQEgkNADAAAAAAAXqRQQjUgH6WjVFi7Jziygi6juBAJeFIciAgMkt17q0sH5xg6K3rXnAJ/seimvzbMNgp2C0JVi/ouuhUcwRAIgIPAaAfgPulkyQ5L6f2KTr7bIEWfBTBowsEyi9Aosr0ECIAsNTyysm/4CHhW4fN4dGC0JCUUedI0Z+0jldWcmiopoASICA3Ro+OqZtsZHiDmLWtJUgMrQj0sNZb5UzjpV/SBrWuRyRzBEAiBO/Hb8owJsvAwLlLhITvCDyb0F4AcJ49xlIdiQcM0ETQIgWHvNFlXDhYjeCl3H9u0Jc/tEAhbTxTgFDR07DdaIcK0BIgID9y09lmY7DqmbCusNfyc8qxGo3jeIXx3dyNkRKtuHFpNHMEQCIBslyLRBXqm+kwjMszeUNWFBX8iZpeihmlo1s47BbMT/AiAYafOyRO+LmBm4x+EWMZ3VDzauxhung7FJAm/598b6mAEBBCIAIHQQ4qnMe1dC7RoA6/AqOG53jareHaC0Fbqu6vBAL08NAQXxUyECL1M7Zn4uo7NuIZYcn+nco0D74K9SEBc6g64DN6sgpXYhAmu1OpjoEL0O5hoO0RZLpsAkeG12VU55PiAtxs6ceMTqIQLVuKfWakH/229MU9YZlAIuiGtPRQAfsVi5XJFk1F+MoyEDJLde6tLB+cYOit615wCf7Hopr82zDYKdgtCVYv6LroUhAy00+JMiAIM0h70pSqIZ3L4AC5+bPYJHmVQUMACfD6VRIQN0aPjqmbbGR4g5i1rSVIDK0I9LDWW+VM46Vf0ga1rkciED9y09lmY7DqmbCusNfyc8qxGo3jeIXx3dyNkRKtuHFpNXrgEHIyIAIHQQ4qnMe1dC7RoA6/AqOG53jareHaC0Fbqu6vBAL08NAQj9zAEFAEcwRAIgIPAaAfgPulkyQ5L6f2KTr7bIEWfBTBowsEyi9Aosr0ECIAsNTyysm/4CHhW4fN4dGC0JCUUedI0Z+0jldWcmiopoAUcwRAIgTvx2/KMCbLwMC5S4SE7wg8m9BeAHCePcZSHYkHDNBE0CIFh7zRZVw4WI3gpdx/btCXP7RAIW08U4BQ0dOw3WiHCtAUcwRAIgGyXItEFeqb6TCMyzN5Q1YUFfyJml6KGaWjWzjsFsxP8CIBhp87JE74uYGbjH4RYxndUPNq7GG6eDsUkCb/n3xvqYAfFTIQIvUztmfi6js24hlhyf6dyjQPvgr1IQFzqDrgM3qyCldiECa7U6mOgQvQ7mGg7RFkumwCR4bXZVTnk+IC3Gzpx4xOohAtW4p9ZqQf/bb0xT1hmUAi6Ia09FAB+xWLlckWTUX4yjIQMkt17q0sH5xg6K3rXnAJ/seimvzbMNgp2C0JVi/ouuhSEDLTT4kyIAgzSHvSlKohncvgALn5s9gkeZVBQwAJ8PpVEhA3Ro+OqZtsZHiDmLWtJUgMrQj0sNZb5UzjpV/SBrWuRyIQP3LT2WZjsOqZsK6w1/JzyrEajeN4hfHd3I2REq24cWk1euAAEBIMToAgAAAAAAF6kUEI1IB+lo1RYuyc4soIuo7gQCXhSHIgIDJLde6tLB+cYOit615wCf7Hopr82zDYKdgtCVYv6LroVIMEUCIQC0teI6jSpNvTYMnaPvHBLHz8xeV78YSKHP0wDLTeIFggIgKJwbaMl8W0lphJppl+GpIda/WuptemyTsvvRxfDZh8IBIgIDdGj46pm2xkeIOYta0lSAytCPSw1lvlTOOlX9IGta5HJHMEQCIAuqur8TVlHrIYOWS8H1DM0ujqJOOPRrTzHHNY/PxsYEAiAq8VxXwyEEb+6DtbhYVffNGPsLI8KursWz162rnUw7XAEiAgP3LT2WZjsOqZsK6w1/JzyrEajeN4hfHd3I2REq24cWk0YwQwIfOF7R8ET9GpC3LilmUZ/oTO3kCtEO33jfcRxTZMaf0gIgQ3PLFN/ia0aSa3ZjSGoXT6at1OmFDaw0JVcdUh5KQskBAQQiACB0EOKpzHtXQu0aAOvwKjhud42q3h2gtBW6rurwQC9PDQEF8VMhAi9TO2Z+LqOzbiGWHJ/p3KNA++CvUhAXOoOuAzerIKV2IQJrtTqY6BC9DuYaDtEWS6bAJHhtdlVOeT4gLcbOnHjE6iEC1bin1mpB/9tvTFPWGZQCLohrT0UAH7FYuVyRZNRfjKMhAyS3XurSwfnGDoretecAn+x6Ka/Nsw2CnYLQlWL+i66FIQMtNPiTIgCDNIe9KUqiGdy+AAufmz2CR5lUFDAAnw+lUSEDdGj46pm2xkeIOYta0lSAytCPSw1lvlTOOlX9IGta5HIhA/ctPZZmOw6pmwrrDX8nPKsRqN43iF8d3cjZESrbhxaTV64BByMiACB0EOKpzHtXQu0aAOvwKjhud42q3h2gtBW6rurwQC9PDQEI/cwBBQBIMEUCIQC0teI6jSpNvTYMnaPvHBLHz8xeV78YSKHP0wDLTeIFggIgKJwbaMl8W0lphJppl+GpIda/WuptemyTsvvRxfDZh8IBRzBEAiALqrq/E1ZR6yGDlkvB9QzNLo6iTjj0a08xxzWPz8bGBAIgKvFcV8MhBG/ug7W4WFX3zRj7CyPCrq7Fs9etq51MO1wBRjBDAh84XtHwRP0akLcuKWZRn+hM7eQK0Q7feN9xHFNkxp/SAiBDc8sU3+JrRpJrdmNIahdPpq3U6YUNrDQlVx1SHkpCyQHxUyECL1M7Zn4uo7NuIZYcn+nco0D74K9SEBc6g64DN6sgpXYhAmu1OpjoEL0O5hoO0RZLpsAkeG12VU55PiAtxs6ceMTqIQLVuKfWakH/229MU9YZlAIuiGtPRQAfsVi5XJFk1F+MoyEDJLde6tLB+cYOit615wCf7Hopr82zDYKdgtCVYv6LroUhAy00+JMiAIM0h70pSqIZ3L4AC5+bPYJHmVQUMACfD6VRIQN0aPjqmbbGR4g5i1rSVIDK0I9LDWW+VM46Vf0ga1rkciED9y09lmY7DqmbCusNfyc8qxGo3jeIXx3dyNkRKtuHFpNXrgABASBAvAMAAAAAABepFBCNSAfpaNUWLsnOLKCLqO4EAl4UhyICAyS3XurSwfnGDoretecAn+x6Ka/Nsw2CnYLQlWL+i66FSDBFAiEA1/g2rzRk8SH4joG6KgolR3Duzs6MRsoqDHsYQFxpOeUCIFJNPgKVhztuek3nslD5goODjy9uH7zyxeCH1IpnVng+ASICA3Ro+OqZtsZHiDmLWtJUgMrQj0sNZb5UzjpV/SBrWuRyRzBEAiB62Ajtue1nb0g5UPhCD/0XTeeMXOhkXIVzV97pSYwJQgIgY1jbyOjos8QBtSmSUsMinsYwUDusy5ipu20YLh4iPJQBIgID9y09lmY7DqmbCusNfyc8qxGo3jeIXx3dyNkRKtuHFpNIMEUCIQDL2gnT2r42FEhLgAiZnR8jsPTIeoJXFwhCFRmtZNR6qgIgOQqJSY75A5yNYU7iL46rrAA2OXN9VKORVqywBKEAQCABAQQiACB0EOKpzHtXQu0aAOvwKjhud42q3h2gtBW6rurwQC9PDQEF8VMhAi9TO2Z+LqOzbiGWHJ/p3KNA++CvUhAXOoOuAzerIKV2IQJrtTqY6BC9DuYaDtEWS6bAJHhtdlVOeT4gLcbOnHjE6iEC1bin1mpB/9tvTFPWGZQCLohrT0UAH7FYuVyRZNRfjKMhAyS3XurSwfnGDoretecAn+x6Ka/Nsw2CnYLQlWL+i66FIQMtNPiTIgCDNIe9KUqiGdy+AAufmz2CR5lUFDAAnw+lUSEDdGj46pm2xkeIOYta0lSAytCPSw1lvlTOOlX9IGta5HIhA/ctPZZmOw6pmwrrDX8nPKsRqN43iF8d3cjZESrbhxaTV64BByMiACB0EOKpzHtXQu0aAOvwKjhud42q3h2gtBW6rurwQC9PDQEI/c4BBQBIMEUCIQDX+DavNGTxIfiOgboqCiVHcO7OzoxGyioMexhAXGk55QIgUk0+ApWHO256TeeyUPmCg4OPL24fvPLF4IfUimdWeD4BRzBEAiB62Ajtue1nb0g5UPhCD/0XTeeMXOhkXIVzV97pSYwJQgIgY1jbyOjos8QBtSmSUsMinsYwUDusy5ipu20YLh4iPJQBSDBFAiEAy9oJ09q+NhRIS4AImZ0fI7D0yHqCVxcIQhUZrWTUeqoCIDkKiUmO+QOcjWFO4i+Oq6wANjlzfVSjkVassAShAEAgAfFTIQIvUztmfi6js24hlhyf6dyjQPvgr1IQFzqDrgM3qyCldiECa7U6mOgQvQ7mGg7RFkumwCR4bXZVTnk+IC3Gzpx4xOohAtW4p9ZqQf/bb0xT1hmUAi6Ia09FAB+xWLlckWTUX4yjIQMkt17q0sH5xg6K3rXnAJ/seimvzbMNgp2C0JVi/ouuhSEDLTT4kyIAgzSHvSlKohncvgALn5s9gkeZVBQwAJ8PpVEhA3Ro+OqZtsZHiDmLWtJUgMrQj0sNZb5UzjpV/SBrWuRyIQP3LT2WZjsOqZsK6w1/JzyrEajeN4hfHd3I2REq24cWk1euAAEBIJDQAwAAAAAAF6kUEI1IB+lo1RYuyc4soIuo7gQCXhSHIgIDJLde6tLB+cYOit615wCf7Hopr82zDYKdgtCVYv6LroVHMEQCIG8RbiU4pfej6nYCvbRERTrOV7THtJ/xiFL83iKmn0STAiAgZE3tv89cnDkXzkUF/NWLu7jgx2aIOIw+oux59Ad89gEiAgN0aPjqmbbGR4g5i1rSVIDK0I9LDWW+VM46Vf0ga1rkckgwRQIhAN3hBE1+lZG9CspFe2Vi99jCfdxp7uT9wahGSKetI7DyAiACfY4axH2e8AC9HxxlUdEv3tF966p1AkRyXFVnFvKOiQEiAgP3LT2WZjsOqZsK6w1/JzyrEajeN4hfHd3I2REq24cWk0gwRQIhAJxmYXVPXR8U+T7JAoacKaZ3LxKmGDOp6ZejLp1kBC2DAiB+9szpX3hTOhHXKyiJqCh/sOqI4JLN/lBZ/0+7Ib0keAEBBCIAIHQQ4qnMe1dC7RoA6/AqOG53jareHaC0Fbqu6vBAL08NAQXxUyECL1M7Zn4uo7NuIZYcn+nco0D74K9SEBc6g64DN6sgpXYhAmu1OpjoEL0O5hoO0RZLpsAkeG12VU55PiAtxs6ceMTqIQLVuKfWakH/229MU9YZlAIuiGtPRQAfsVi5XJFk1F+MoyEDJLde6tLB+cYOit615wCf7Hopr82zDYKdgtCVYv6LroUhAy00+JMiAIM0h70pSqIZ3L4AC5+bPYJHmVQUMACfD6VRIQN0aPjqmbbGR4g5i1rSVIDK0I9LDWW+VM46Vf0ga1rkciED9y09lmY7DqmbCusNfyc8qxGo3jeIXx3dyNkRKtuHFpNXrgEHIyIAIHQQ4qnMe1dC7RoA6/AqOG53jareHaC0Fbqu6vBAL08NAQj9zgEFAEcwRAIgbxFuJTil96PqdgK9tERFOs5XtMe0n/GIUvzeIqafRJMCICBkTe2/z1ycORfORQX81Yu7uODHZog4jD6i7Hn0B3z2AUgwRQIhAN3hBE1+lZG9CspFe2Vi99jCfdxp7uT9wahGSKetI7DyAiACfY4axH2e8AC9HxxlUdEv3tF966p1AkRyXFVnFvKOiQFIMEUCIQCcZmF1T10fFPk+yQKGnCmmdy8SphgzqemXoy6dZAQtgwIgfvbM6V94UzoR1ysoiagof7DqiOCSzf5QWf9PuyG9JHgB8VMhAi9TO2Z+LqOzbiGWHJ/p3KNA++CvUhAXOoOuAzerIKV2IQJrtTqY6BC9DuYaDtEWS6bAJHhtdlVOeT4gLcbOnHjE6iEC1bin1mpB/9tvTFPWGZQCLohrT0UAH7FYuVyRZNRfjKMhAyS3XurSwfnGDoretecAn+x6Ka/Nsw2CnYLQlWL+i66FIQMtNPiTIgCDNIe9KUqiGdy+AAufmz2CR5lUFDAAnw+lUSEDdGj46pm2xkeIOYta0lSAytCPSw1lvlTOOlX9IGta5HIhA/ctPZZmOw6pmwrrDX8nPKsRqN43iF8d3cjZESrbhxaTV64AAQEgkNADAAAAAAAXqRQQjUgH6WjVFi7Jziygi6juBAJeFIciAgMkt17q0sH5xg6K3rXnAJ/seimvzbMNgp2C0JVi/ouuhUgwRQIhAJe1Byz1N0Z9WujF/vKFS9aHSpDQmC7lx2nvWACr5RCHAiAvVK+MUJuIIAh5+W5tZI/DMoN2V72My/8Mb/Qf29jsUgEiAgN0aPjqmbbGR4g5i1rSVIDK0I9LDWW+VM46Vf0ga1rkckcwRAIgQvtUBnniirlsWgZ28sS44likUKFj+BjKIGxU7x2UFnACIDj3WbTWwLNVjZmCjKlQLF9IxuUcRHFkn+psFxjgmmhLASICA/ctPZZmOw6pmwrrDX8nPKsRqN43iF8d3cjZESrbhxaTRzBEAiAdNVtbZok1kacUYjwCD4G0iAIZoVIHYwcVhy+bcsKlEQIgVfmeZ9ATULCT21SF7AGuRsvPFFQNvZxOHj8nYCrFr3IBAQQiACB0EOKpzHtXQu0aAOvwKjhud42q3h2gtBW6rurwQC9PDQEF8VMhAi9TO2Z+LqOzbiGWHJ/p3KNA++CvUhAXOoOuAzerIKV2IQJrtTqY6BC9DuYaDtEWS6bAJHhtdlVOeT4gLcbOnHjE6iEC1bin1mpB/9tvTFPWGZQCLohrT0UAH7FYuVyRZNRfjKMhAyS3XurSwfnGDoretecAn+x6Ka/Nsw2CnYLQlWL+i66FIQMtNPiTIgCDNIe9KUqiGdy+AAufmz2CR5lUFDAAnw+lUSEDdGj46pm2xkeIOYta0lSAytCPSw1lvlTOOlX9IGta5HIhA/ctPZZmOw6pmwrrDX8nPKsRqN43iF8d3cjZESrbhxaTV64BByMiACB0EOKpzHtXQu0aAOvwKjhud42q3h2gtBW6rurwQC9PDQEI/c0BBQBIMEUCIQCXtQcs9TdGfVroxf7yhUvWh0qQ0Jgu5cdp71gAq+UQhwIgL1SvjFCbiCAIeflubWSPwzKDdle9jMv/DG/0H9vY7FIBRzBEAiBC+1QGeeKKuWxaBnbyxLjiWKRQoWP4GMogbFTvHZQWcAIgOPdZtNbAs1WNmYKMqVAsX0jG5RxEcWSf6mwXGOCaaEsBRzBEAiAdNVtbZok1kacUYjwCD4G0iAIZoVIHYwcVhy+bcsKlEQIgVfmeZ9ATULCT21SF7AGuRsvPFFQNvZxOHj8nYCrFr3IB8VMhAi9TO2Z+LqOzbiGWHJ/p3KNA++CvUhAXOoOuAzerIKV2IQJrtTqY6BC9DuYaDtEWS6bAJHhtdlVOeT4gLcbOnHjE6iEC1bin1mpB/9tvTFPWGZQCLohrT0UAH7FYuVyRZNRfjKMhAyS3XurSwfnGDoretecAn+x6Ka/Nsw2CnYLQlWL+i66FIQMtNPiTIgCDNIe9KUqiGdy+AAufmz2CR5lUFDAAnw+lUSEDdGj46pm2xkeIOYta0lSAytCPSw1lvlTOOlX9IGta5HIhA/ctPZZmOw6pmwrrDX8nPKsRqN43iF8d3cjZESrbhxaTV64AAQEgoMsCAAAAAAAXqRQQjUgH6WjVFi7Jziygi6juBAJeFIciAgMkt17q0sH5xg6K3rXnAJ/seimvzbMNgp2C0JVi/ouuhUgwRQIhAIaWIg/RLaQ2Kv2PJZBwrVsK6QkGO5oc6Gax5pMUJu1HAiAGLpU1ShiqbbGpnC1t6K0zYWMPfm5XuHKNfI/Z5XwJrwEiAgN0aPjqmbbGR4g5i1rSVIDK0I9LDWW+VM46Vf0ga1rkckgwRQIhALkJ3WI0WfmEDEWB8yN8J1jqyY92BoFGyJOmB8nAbZNeAiAgzrzyb2wLaVyl4LXFHE40GTa6HkmopRDN+35zJZb2yQEiAgP3LT2WZjsOqZsK6w1/JzyrEajeN4hfHd3I2REq24cWk0gwRQIhANKRAxXD6w6U66SVPj+6JtU2u10UttnkCIoQZKBadntDAiAvcgibqGLyogPYkZgtI72qs2coYX3zrOfLOdPDOtaYhgEBBCIAIHQQ4qnMe1dC7RoA6/AqOG53jareHaC0Fbqu6vBAL08NAQXxUyECL1M7Zn4uo7NuIZYcn+nco0D74K9SEBc6g64DN6sgpXYhAmu1OpjoEL0O5hoO0RZLpsAkeG12VU55PiAtxs6ceMTqIQLVuKfWakH/229MU9YZlAIuiGtPRQAfsVi5XJFk1F+MoyEDJLde6tLB+cYOit615wCf7Hopr82zDYKdgtCVYv6LroUhAy00+JMiAIM0h70pSqIZ3L4AC5+bPYJHmVQUMACfD6VRIQN0aPjqmbbGR4g5i1rSVIDK0I9LDWW+VM46Vf0ga1rkciED9y09lmY7DqmbCusNfyc8qxGo3jeIXx3dyNkRKtuHFpNXrgEHIyIAIHQQ4qnMe1dC7RoA6/AqOG53jareHaC0Fbqu6vBAL08NAQj9zwEFAEgwRQIhAIaWIg/RLaQ2Kv2PJZBwrVsK6QkGO5oc6Gax5pMUJu1HAiAGLpU1ShiqbbGpnC1t6K0zYWMPfm5XuHKNfI/Z5XwJrwFIMEUCIQC5Cd1iNFn5hAxFgfMjfCdY6smPdgaBRsiTpgfJwG2TXgIgIM688m9sC2lcpeC1xRxONBk2uh5JqKUQzft+cyWW9skBSDBFAiEA0pEDFcPrDpTrpJU+P7om1Ta7XRS22eQIihBkoFp2e0MCIC9yCJuoYvKiA9iRmC0jvaqzZyhhffOs58s508M61piGAfFTIQIvUztmfi6js24hlhyf6dyjQPvgr1IQFzqDrgM3qyCldiECa7U6mOgQvQ7mGg7RFkumwCR4bXZVTnk+IC3Gzpx4xOohAtW4p9ZqQf/bb0xT1hmUAi6Ia09FAB+xWLlckWTUX4yjIQMkt17q0sH5xg6K3rXnAJ/seimvzbMNgp2C0JVi/ouuhSEDLTT4kyIAgzSHvSlKohncvgALn5s9gkeZVBQwAJ8PpVEhA3Ro+OqZtsZHiDmLWtJUgMrQj0sNZb5UzjpV/SBrWuRyIQP3LT2WZjsOqZsK6w1/JzyrEajeN4hfHd3I2REq24cWk1euAAEBIEC8AwAAAAAAF6kUEI1IB+lo1RYuyc4soIuo7gQCXhSHIgIDJLde6tLB+cYOit615wCf7Hopr82zDYKdgtCVYv6LroVHMEQCIDcrSuHCIy8dYDwYX2fk04o7gNLgAKGPIL9TJMfa1HwTAiAFTr+kHxCeNPAad8ueul5ZqEU0aasIHitJQMmMgepoDwEiAgN0aPjqmbbGR4g5i1rSVIDK0I9LDWW+VM46Vf0ga1rkckgwRQIhANyomNej5S0KfovKPU29hzDyylO/E1QGJXlrvV6QLj/NAiAprLPC3aNM5jQ6gxF7Uv7kgf+x9Tb4/OEIMvDdEal/wgEiAgP3LT2WZjsOqZsK6w1/JzyrEajeN4hfHd3I2REq24cWk0cwRAIgDBhvIR9ZZzL5bRf6PMMbpi1v7/13gK/CJScbtapq3egCIFW2hwPcFDNGRfI25E8qxgSKaeIJmF+3nKEN5aX+ct/CAQEEIgAgdBDiqcx7V0LtGgDr8Co4bneNqt4doLQVuq7q8EAvTw0BBfFTIQIvUztmfi6js24hlhyf6dyjQPvgr1IQFzqDrgM3qyCldiECa7U6mOgQvQ7mGg7RFkumwCR4bXZVTnk+IC3Gzpx4xOohAtW4p9ZqQf/bb0xT1hmUAi6Ia09FAB+xWLlckWTUX4yjIQMkt17q0sH5xg6K3rXnAJ/seimvzbMNgp2C0JVi/ouuhSEDLTT4kyIAgzSHvSlKohncvgALn5s9gkeZVBQwAJ8PpVEhA3Ro+OqZtsZHiDmLWtJUgMrQj0sNZb5UzjpV/SBrWuRyIQP3LT2WZjsOqZsK6w1/JzyrEajeN4hfHd3I2REq24cWk1euAQcjIgAgdBDiqcx7V0LtGgDr8Co4bneNqt4doLQVuq7q8EAvTw0BCP3NAQUARzBEAiA3K0rhwiMvHWA8GF9n5NOKO4DS4AChjyC/UyTH2tR8EwIgBU6/pB8QnjTwGnfLnrpeWahFNGmrCB4rSUDJjIHqaA8BSDBFAiEA3KiY16PlLQp+i8o9Tb2HMPLKU78TVAYleWu9XpAuP80CICmss8Ldo0zmNDqDEXtS/uSB/7H1Nvj84Qgy8N0RqX/CAUcwRAIgDBhvIR9ZZzL5bRf6PMMbpi1v7/13gK/CJScbtapq3egCIFW2hwPcFDNGRfI25E8qxgSKaeIJmF+3nKEN5aX+ct/CAfFTIQIvUztmfi6js24hlhyf6dyjQPvgr1IQFzqDrgM3qyCldiECa7U6mOgQvQ7mGg7RFkumwCR4bXZVTnk+IC3Gzpx4xOohAtW4p9ZqQf/bb0xT1hmUAi6Ia09FAB+xWLlckWTUX4yjIQMkt17q0sH5xg6K3rXnAJ/seimvzbMNgp2C0JVi/ouuhSEDLTT4kyIAgzSHvSlKohncvgALn5s9gkeZVBQwAJ8PpVEhA3Ro+OqZtsZHiDmLWtJUgMrQj0sNZb5UzjpV/SBrWuRyIQP3LT2WZjsOqZsK6w1/JzyrEajeN4hfHd3I2REq24cWk1euAAEBIGB0AwAAAAAAF6kUEI1IB+lo1RYuyc4soIuo7gQCXhSHIgIDJLde6tLB+cYOit615wCf7Hopr82zDYKdgtCVYv6LroVIMEUCIQDiOpeNLn9TUH52eaL716+dvJG3izzKgeNikj6rG0UWZgIgJJPWxBKq6wWUoOoLfNsdvXqehOqMzAPdvWcRVAWE3mgBIgIDdGj46pm2xkeIOYta0lSAytCPSw1lvlTOOlX9IGta5HJIMEUCIQCbQIiX6cMvg8tBX+NGPcUlrlNOD2NcOIYem7f0JTn9eAIgBeTpWQU5o3+Gj0pNdcDMZCOfIDRVxqUj4N8wdNsxXAcBIgID9y09lmY7DqmbCusNfyc8qxGo3jeIXx3dyNkRKtuHFpNIMEUCIQCJSwnjDM8L3cLDOvuPEZBu/ZNvy8nccMZgquCCBsZ/RQIgdyRS2fD9JzLsfj0cY5ISJlLs63R4uEd4ZHv25a/2ysgBAQQiACB0EOKpzHtXQu0aAOvwKjhud42q3h2gtBW6rurwQC9PDQEF8VMhAi9TO2Z+LqOzbiGWHJ/p3KNA++CvUhAXOoOuAzerIKV2IQJrtTqY6BC9DuYaDtEWS6bAJHhtdlVOeT4gLcbOnHjE6iEC1bin1mpB/9tvTFPWGZQCLohrT0UAH7FYuVyRZNRfjKMhAyS3XurSwfnGDoretecAn+x6Ka/Nsw2CnYLQlWL+i66FIQMtNPiTIgCDNIe9KUqiGdy+AAufmz2CR5lUFDAAnw+lUSEDdGj46pm2xkeIOYta0lSAytCPSw1lvlTOOlX9IGta5HIhA/ctPZZmOw6pmwrrDX8nPKsRqN43iF8d3cjZESrbhxaTV64BByMiACB0EOKpzHtXQu0aAOvwKjhud42q3h2gtBW6rurwQC9PDQEI/c8BBQBIMEUCIQDiOpeNLn9TUH52eaL716+dvJG3izzKgeNikj6rG0UWZgIgJJPWxBKq6wWUoOoLfNsdvXqehOqMzAPdvWcRVAWE3mgBSDBFAiEAm0CIl+nDL4PLQV/jRj3FJa5TTg9jXDiGHpu39CU5/XgCIAXk6VkFOaN/ho9KTXXAzGQjnyA0VcalI+DfMHTbMVwHAUgwRQIhAIlLCeMMzwvdwsM6+48RkG79k2/LydxwxmCq4IIGxn9FAiB3JFLZ8P0nMux+PRxjkhImUuzrdHi4R3hke/blr/bKyAHxUyECL1M7Zn4uo7NuIZYcn+nco0D74K9SEBc6g64DN6sgpXYhAmu1OpjoEL0O5hoO0RZLpsAkeG12VU55PiAtxs6ceMTqIQLVuKfWakH/229MU9YZlAIuiGtPRQAfsVi5XJFk1F+MoyEDJLde6tLB+cYOit615wCf7Hopr82zDYKdgtCVYv6LroUhAy00+JMiAIM0h70pSqIZ3L4AC5+bPYJHmVQUMACfD6VRIQN0aPjqmbbGR4g5i1rSVIDK0I9LDWW+VM46Vf0ga1rkciED9y09lmY7DqmbCusNfyc8qxGo3jeIXx3dyNkRKtuHFpNXrgABASAUkwMAAAAAABepFBCNSAfpaNUWLsnOLKCLqO4EAl4UhyICAyS3XurSwfnGDoretecAn+x6Ka/Nsw2CnYLQlWL+i66FSDBFAiEAgOQshynIa1z5uOeZe1LzWwJJnpfNw0ioRQU8LNFuHzsCIC0fRCyCT/Lbv7aOFAPaV2MPE3fcSRbHoatLebaur3dHASICA3Ro+OqZtsZHiDmLWtJUgMrQj0sNZb5UzjpV/SBrWuRyRzBEAiAkRkDvUp/7DYDkjA2PqbL6hYsXaQfhjN34JQxofNQ8jQIgZlndSNbEE6ftp1M/+DOmi8G/eBO+iux5skc2FDFR/qkBIgID9y09lmY7DqmbCusNfyc8qxGo3jeIXx3dyNkRKtuHFpNIMEUCIQDvcrYYdDvLUpxX9E8CXV64vL+71+Ae1bXGZsUrERWeJAIgTUwCgbqNXQBv8rfs1plIbW0WgRuXRfZykTAfivyNZDABAQQiACB0EOKpzHtXQu0aAOvwKjhud42q3h2gtBW6rurwQC9PDQEF8VMhAi9TO2Z+LqOzbiGWHJ/p3KNA++CvUhAXOoOuAzerIKV2IQJrtTqY6BC9DuYaDtEWS6bAJHhtdlVOeT4gLcbOnHjE6iEC1bin1mpB/9tvTFPWGZQCLohrT0UAH7FYuVyRZNRfjKMhAyS3XurSwfnGDoretecAn+x6Ka/Nsw2CnYLQlWL+i66FIQMtNPiTIgCDNIe9KUqiGdy+AAufmz2CR5lUFDAAnw+lUSEDdGj46pm2xkeIOYta0lSAytCPSw1lvlTOOlX9IGta5HIhA/ctPZZmOw6pmwrrDX8nPKsRqN43iF8d3cjZESrbhxaTV64BByMiACB0EOKpzHtXQu0aAOvwKjhud42q3h2gtBW6rurwQC9PDQEI/c4BBQBIMEUCIQCA5CyHKchrXPm455l7UvNbAkmel83DSKhFBTws0W4fOwIgLR9ELIJP8tu/to4UA9pXYw8Td9xJFsehq0t5tq6vd0cBRzBEAiAkRkDvUp/7DYDkjA2PqbL6hYsXaQfhjN34JQxofNQ8jQIgZlndSNbEE6ftp1M/+DOmi8G/eBO+iux5skc2FDFR/qkBSDBFAiEA73K2GHQ7y1KcV/RPAl1euLy/u9fgHtW1xmbFKxEVniQCIE1MAoG6jV0Ab/K37NaZSG1tFoEbl0X2cpEwH4r8jWQwAfFTIQIvUztmfi6js24hlhyf6dyjQPvgr1IQFzqDrgM3qyCldiECa7U6mOgQvQ7mGg7RFkumwCR4bXZVTnk+IC3Gzpx4xOohAtW4p9ZqQf/bb0xT1hmUAi6Ia09FAB+xWLlckWTUX4yjIQMkt17q0sH5xg6K3rXnAJ/seimvzbMNgp2C0JVi/ouuhSEDLTT4kyIAgzSHvSlKohncvgALn5s9gkeZVBQwAJ8PpVEhA3Ro+OqZtsZHiDmLWtJUgMrQj0sNZb5UzjpV/SBrWuRyIQP3LT2WZjsOqZsK6w1/JzyrEajeN4hfHd3I2REq24cWk1euAAEBIJDQAwAAAAAAF6kUEI1IB+lo1RYuyc4soIuo7gQCXhSHIgIDJLde6tLB+cYOit615wCf7Hopr82zDYKdgtCVYv6LroVIMEUCIQC2uoCNKThiMkY4hS0N/RjgjTL9xHyXfpUu8YRhG8IpsAIgbsPsv6IVfIOfkOjeLCOZ0M3HaY4y2VGjtlimyYKxajwBIgIDdGj46pm2xkeIOYta0lSAytCPSw1lvlTOOlX9IGta5HJHMEQCID4kiDHLNloY2scrbYxkbLYl0tztci2c8z6OCcd4tANmAiB063HT9xQXn3hxyCbkSQbspPuggC6/o/rCWj3pyZgtqQEiAgP3LT2WZjsOqZsK6w1/JzyrEajeN4hfHd3I2REq24cWk0gwRQIhANabxBh+izQPr11tLskTDYo0TPBwW/FCnUPd4gYzdkZXAiAKfVxwkrXQEPWKfUadqfkuYTO9Ts85LKB4g+3ul+EcQQEBBCIAIHQQ4qnMe1dC7RoA6/AqOG53jareHaC0Fbqu6vBAL08NAQXxUyECL1M7Zn4uo7NuIZYcn+nco0D74K9SEBc6g64DN6sgpXYhAmu1OpjoEL0O5hoO0RZLpsAkeG12VU55PiAtxs6ceMTqIQLVuKfWakH/229MU9YZlAIuiGtPRQAfsVi5XJFk1F+MoyEDJLde6tLB+cYOit615wCf7Hopr82zDYKdgtCVYv6LroUhAy00+JMiAIM0h70pSqIZ3L4AC5+bPYJHmVQUMACfD6VRIQN0aPjqmbbGR4g5i1rSVIDK0I9LDWW+VM46Vf0ga1rkciED9y09lmY7DqmbCusNfyc8qxGo3jeIXx3dyNkRKtuHFpNXrgEHIyIAIHQQ4qnMe1dC7RoA6/AqOG53jareHaC0Fbqu6vBAL08NAQj9zgEFAEgwRQIhALa6gI0pOGIyRjiFLQ39GOCNMv3EfJd+lS7xhGEbwimwAiBuw+y/ohV8g5+Q6N4sI5nQzcdpjjLZUaO2WKbJgrFqPAFHMEQCID4kiDHLNloY2scrbYxkbLYl0tztci2c8z6OCcd4tANmAiB063HT9xQXn3hxyCbkSQbspPuggC6/o/rCWj3pyZgtqQFIMEUCIQDWm8QYfos0D69dbS7JEw2KNEzwcFvxQp1D3eIGM3ZGVwIgCn1ccJK10BD1in1Gnan5LmEzvU7POSygeIPt7pfhHEEB8VMhAi9TO2Z+LqOzbiGWHJ/p3KNA++CvUhAXOoOuAzerIKV2IQJrtTqY6BC9DuYaDtEWS6bAJHhtdlVOeT4gLcbOnHjE6iEC1bin1mpB/9tvTFPWGZQCLohrT0UAH7FYuVyRZNRfjKMhAyS3XurSwfnGDoretecAn+x6Ka/Nsw2CnYLQlWL+i66FIQMtNPiTIgCDNIe9KUqiGdy+AAufmz2CR5lUFDAAnw+lUSEDdGj46pm2xkeIOYta0lSAytCPSw1lvlTOOlX9IGta5HIhA/ctPZZmOw6pmwrrDX8nPKsRqN43iF8d3cjZESrbhxaTV64AAQEgkNADAAAAAAAXqRQQjUgH6WjVFi7Jziygi6juBAJeFIciAgMkt17q0sH5xg6K3rXnAJ/seimvzbMNgp2C0JVi/ouuhUgwRQIhAJi8clrjwM1svGNRYYAjbDVdW1Dy/qcwbzRdkK22ZxmJAiAWYiFtqswmynT8tMxXCkCUXiTwO5S47DzB+c95bEcQRwEiAgN0aPjqmbbGR4g5i1rSVIDK0I9LDWW+VM46Vf0ga1rkckgwRQIhAOYEdTY4CF6uEbZUq+0jHn2wWrRS+hSE9Pw/owayR76qAiBToNj2JBrMhiZmEDC4pom+5uq0lLkA1i3sU0Q/sGeBZgEiAgP3LT2WZjsOqZsK6w1/JzyrEajeN4hfHd3I2REq24cWk0gwRQIhALTX4VG5eJlIS4uNNWcBHWDuEDmHuJwdeLQNY3O0KaaYAiBpVSpEgvu8pcWo0Hedv9D9qZLnFGCx9ITt0462qLbNhAEBBCIAIHQQ4qnMe1dC7RoA6/AqOG53jareHaC0Fbqu6vBAL08NAQXxUyECL1M7Zn4uo7NuIZYcn+nco0D74K9SEBc6g64DN6sgpXYhAmu1OpjoEL0O5hoO0RZLpsAkeG12VU55PiAtxs6ceMTqIQLVuKfWakH/229MU9YZlAIuiGtPRQAfsVi5XJFk1F+MoyEDJLde6tLB+cYOit615wCf7Hopr82zDYKdgtCVYv6LroUhAy00+JMiAIM0h70pSqIZ3L4AC5+bPYJHmVQUMACfD6VRIQN0aPjqmbbGR4g5i1rSVIDK0I9LDWW+VM46Vf0ga1rkciED9y09lmY7DqmbCusNfyc8qxGo3jeIXx3dyNkRKtuHFpNXrgEHIyIAIHQQ4qnMe1dC7RoA6/AqOG53jareHaC0Fbqu6vBAL08NAQj9zwEFAEgwRQIhAJi8clrjwM1svGNRYYAjbDVdW1Dy/qcwbzRdkK22ZxmJAiAWYiFtqswmynT8tMxXCkCUXiTwO5S47DzB+c95bEcQRwFIMEUCIQDmBHU2OAherhG2VKvtIx59sFq0UvoUhPT8P6MGske+qgIgU6DY9iQazIYmZhAwuKaJvubqtJS5ANYt7FNEP7BngWYBSDBFAiEAtNfhUbl4mUhLi401ZwEdYO4QOYe4nB14tA1jc7QpppgCIGlVKkSC+7ylxajQd52/0P2pkucUYLH0hO3Tjraots2EAfFTIQIvUztmfi6js24hlhyf6dyjQPvgr1IQFzqDrgM3qyCldiECa7U6mOgQvQ7mGg7RFkumwCR4bXZVTnk+IC3Gzpx4xOohAtW4p9ZqQf/bb0xT1hmUAi6Ia09FAB+xWLlckWTUX4yjIQMkt17q0sH5xg6K3rXnAJ/seimvzbMNgp2C0JVi/ouuhSEDLTT4kyIAgzSHvSlKohncvgALn5s9gkeZVBQwAJ8PpVEhA3Ro+OqZtsZHiDmLWtJUgMrQj0sNZb5UzjpV/SBrWuRyIQP3LT2WZjsOqZsK6w1/JzyrEajeN4hfHd3I2REq24cWk1euAAEBIFCxAwAAAAAAF6kUEI1IB+lo1RYuyc4soIuo7gQCXhSHIgIDJLde6tLB+cYOit615wCf7Hopr82zDYKdgtCVYv6LroVIMEUCIQCIM5+AE0LNL0dGLIWwwWL/sGLD9w4uqZBPD5wytiXE5QIgC7bB/hWaroji6p9U7dKeSwoXSlTpLJ6eTLl/ju1N/zYBIgIDdGj46pm2xkeIOYta0lSAytCPSw1lvlTOOlX9IGta5HJHMEQCIEiYTF/w3BSS0raWSeD5sZ3+xAVMq2wi3lhthIPrNYvEAiAfIvJGodRLNO//Rtdo8DFkvtx7Ea/lzWADz8ylHwDrywEiAgP3LT2WZjsOqZsK6w1/JzyrEajeN4hfHd3I2REq24cWk0gwRQIhANpOLX/6I5W/tzbXnGFOC1aIjTtsfT35FxzL6jxD0pKvAiA9vXRG9jderWI4cLIi0Q0rCmknKxY+Fm+bULY00JEZAgEBBCIAIHQQ4qnMe1dC7RoA6/AqOG53jareHaC0Fbqu6vBAL08NAQXxUyECL1M7Zn4uo7NuIZYcn+nco0D74K9SEBc6g64DN6sgpXYhAmu1OpjoEL0O5hoO0RZLpsAkeG12VU55PiAtxs6ceMTqIQLVuKfWakH/229MU9YZlAIuiGtPRQAfsVi5XJFk1F+MoyEDJLde6tLB+cYOit615wCf7Hopr82zDYKdgtCVYv6LroUhAy00+JMiAIM0h70pSqIZ3L4AC5+bPYJHmVQUMACfD6VRIQN0aPjqmbbGR4g5i1rSVIDK0I9LDWW+VM46Vf0ga1rkciED9y09lmY7DqmbCusNfyc8qxGo3jeIXx3dyNkRKtuHFpNXrgEHIyIAIHQQ4qnMe1dC7RoA6/AqOG53jareHaC0Fbqu6vBAL08NAQj9zgEFAEgwRQIhAIgzn4ATQs0vR0YshbDBYv+wYsP3Di6pkE8PnDK2JcTlAiALtsH+FZquiOLqn1Tt0p5LChdKVOksnp5MuX+O7U3/NgFHMEQCIEiYTF/w3BSS0raWSeD5sZ3+xAVMq2wi3lhthIPrNYvEAiAfIvJGodRLNO//Rtdo8DFkvtx7Ea/lzWADz8ylHwDrywFIMEUCIQDaTi1/+iOVv7c215xhTgtWiI07bH09+Rccy+o8Q9KSrwIgPb10RvY3Xq1iOHCyItENKwppJysWPhZvm1C2NNCRGQIB8VMhAi9TO2Z+LqOzbiGWHJ/p3KNA++CvUhAXOoOuAzerIKV2IQJrtTqY6BC9DuYaDtEWS6bAJHhtdlVOeT4gLcbOnHjE6iEC1bin1mpB/9tvTFPWGZQCLohrT0UAH7FYuVyRZNRfjKMhAyS3XurSwfnGDoretecAn+x6Ka/Nsw2CnYLQlWL+i66FIQMtNPiTIgCDNIe9KUqiGdy+AAufmz2CR5lUFDAAnw+lUSEDdGj46pm2xkeIOYta0lSAytCPSw1lvlTOOlX9IGta5HIhA/ctPZZmOw6pmwrrDX8nPKsRqN43iF8d3cjZESrbhxaTV64AAQEgBOIAAAAAAAAXqRQQjUgH6WjVFi7Jziygi6juBAJeFIciAgMkt17q0sH5xg6K3rXnAJ/seimvzbMNgp2C0JVi/ouuhUcwRAIgZhRZTYdYBLBTgCCXf6UFkn31RHY7ed51EEfODPTP3FgCIBTu3pHyCvvQg2Z8ooA9qs4HQyFDy2wVWER6sRW9qEsTASICA3Ro+OqZtsZHiDmLWtJUgMrQj0sNZb5UzjpV/SBrWuRySDBFAiEAp20ai85KnRTfxfhULKMBZBO65gJ6lCyoUw01O3BbO3gCIHs5mPC4WIxiHmbHCDrIClZ6hfA5E741zGRJNsTl4i2aASICA/ctPZZmOw6pmwrrDX8nPKsRqN43iF8d3cjZESrbhxaTRzBEAiB0HiwaMfMG+/AtVIjNS6AJR2TcDtLEpLNlC7idavov+QIgRd3RJsUWYv9v+RSf3D6SzetUH5s6ua9RiKNVi4BQ6+cBAQQiACB0EOKpzHtXQu0aAOvwKjhud42q3h2gtBW6rurwQC9PDQEF8VMhAi9TO2Z+LqOzbiGWHJ/p3KNA++CvUhAXOoOuAzerIKV2IQJrtTqY6BC9DuYaDtEWS6bAJHhtdlVOeT4gLcbOnHjE6iEC1bin1mpB/9tvTFPWGZQCLohrT0UAH7FYuVyRZNRfjKMhAyS3XurSwfnGDoretecAn+x6Ka/Nsw2CnYLQlWL+i66FIQMtNPiTIgCDNIe9KUqiGdy+AAufmz2CR5lUFDAAnw+lUSEDdGj46pm2xkeIOYta0lSAytCPSw1lvlTOOlX9IGta5HIhA/ctPZZmOw6pmwrrDX8nPKsRqN43iF8d3cjZESrbhxaTV64BByMiACB0EOKpzHtXQu0aAOvwKjhud42q3h2gtBW6rurwQC9PDQEI/c0BBQBHMEQCIGYUWU2HWASwU4Agl3+lBZJ99UR2O3nedRBHzgz0z9xYAiAU7t6R8gr70INmfKKAParOB0MhQ8tsFVhEerEVvahLEwFIMEUCIQCnbRqLzkqdFN/F+FQsowFkE7rmAnqULKhTDTU7cFs7eAIgezmY8LhYjGIeZscIOsgKVnqF8DkTvjXMZEk2xOXiLZoBRzBEAiB0HiwaMfMG+/AtVIjNS6AJR2TcDtLEpLNlC7idavov+QIgRd3RJsUWYv9v+RSf3D6SzetUH5s6ua9RiKNVi4BQ6+cB8VMhAi9TO2Z+LqOzbiGWHJ/p3KNA++CvUhAXOoOuAzerIKV2IQJrtTqY6BC9DuYaDtEWS6bAJHhtdlVOeT4gLcbOnHjE6iEC1bin1mpB/9tvTFPWGZQCLohrT0UAH7FYuVyRZNRfjKMhAyS3XurSwfnGDoretecAn+x6Ka/Nsw2CnYLQlWL+i66FIQMtNPiTIgCDNIe9KUqiGdy+AAufmz2CR5lUFDAAnw+lUSEDdGj46pm2xkeIOYta0lSAytCPSw1lvlTOOlX9IGta5HIhA/ctPZZmOw6pmwrrDX8nPKsRqN43iF8d3cjZESrbhxaTV64AAQEgQA0DAAAAAAAXqRQQjUgH6WjVFi7Jziygi6juBAJeFIciAgMkt17q0sH5xg6K3rXnAJ/seimvzbMNgp2C0JVi/ouuhUgwRQIhALcITbBJx25ndqVAny1w6130pNTZTe/v6eWt87SOY3isAiAOp4eItSRav49fOE3+HsF8eJlyImn1MLEJiBxdsyhkhwEiAgN0aPjqmbbGR4g5i1rSVIDK0I9LDWW+VM46Vf0ga1rkckgwRQIhAJ4PzjzZbK3tAb1V7af8jEPd3PLA+7BaeyyaBfofoNlPAiBFkygCOx0q49gnpuwe61MupyY/Fcp4ZsAZWzp42qSoNQEiAgP3LT2WZjsOqZsK6w1/JzyrEajeN4hfHd3I2REq24cWk0cwRAIgH8CbB/1fbi7RGIVFpJP91vbTIkdOqJ01WwP/tAHRX1MCIFj14hUjOIFgXLqD1Ztvftgxz4Oa8iv+4YmRtrXHjC6JAQEEIgAgdBDiqcx7V0LtGgDr8Co4bneNqt4doLQVuq7q8EAvTw0BBfFTIQIvUztmfi6js24hlhyf6dyjQPvgr1IQFzqDrgM3qyCldiECa7U6mOgQvQ7mGg7RFkumwCR4bXZVTnk+IC3Gzpx4xOohAtW4p9ZqQf/bb0xT1hmUAi6Ia09FAB+xWLlckWTUX4yjIQMkt17q0sH5xg6K3rXnAJ/seimvzbMNgp2C0JVi/ouuhSEDLTT4kyIAgzSHvSlKohncvgALn5s9gkeZVBQwAJ8PpVEhA3Ro+OqZtsZHiDmLWtJUgMrQj0sNZb5UzjpV/SBrWuRyIQP3LT2WZjsOqZsK6w1/JzyrEajeN4hfHd3I2REq24cWk1euAQcjIgAgdBDiqcx7V0LtGgDr8Co4bneNqt4doLQVuq7q8EAvTw0BCP3OAQUASDBFAiEAtwhNsEnHbmd2pUCfLXDrXfSk1NlN7+/p5a3ztI5jeKwCIA6nh4i1JFq/j184Tf4ewXx4mXIiafUwsQmIHF2zKGSHAUgwRQIhAJ4PzjzZbK3tAb1V7af8jEPd3PLA+7BaeyyaBfofoNlPAiBFkygCOx0q49gnpuwe61MupyY/Fcp4ZsAZWzp42qSoNQFHMEQCIB/Amwf9X24u0RiFRaST/db20yJHTqidNVsD/7QB0V9TAiBY9eIVIziBYFy6g9Wbb37YMc+DmvIr/uGJkba1x4wuiQHxUyECL1M7Zn4uo7NuIZYcn+nco0D74K9SEBc6g64DN6sgpXYhAmu1OpjoEL0O5hoO0RZLpsAkeG12VU55PiAtxs6ceMTqIQLVuKfWakH/229MU9YZlAIuiGtPRQAfsVi5XJFk1F+MoyEDJLde6tLB+cYOit615wCf7Hopr82zDYKdgtCVYv6LroUhAy00+JMiAIM0h70pSqIZ3L4AC5+bPYJHmVQUMACfD6VRIQN0aPjqmbbGR4g5i1rSVIDK0I9LDWW+VM46Vf0ga1rkciED9y09lmY7DqmbCusNfyc8qxGo3jeIXx3dyNkRKtuHFpNXrgABASCIkgMAAAAAABepFBCNSAfpaNUWLsnOLKCLqO4EAl4UhyICAyS3XurSwfnGDoretecAn+x6Ka/Nsw2CnYLQlWL+i66FRzBEAiBDugO7p7KtJGKocdosR80FvkGdE7LFEKLR3nAjqgoR5gIgVw9P3kUPCFF9d6eBvCjn5Y/YJdgVNNW6uO6CBgdH+QsBIgIDdGj46pm2xkeIOYta0lSAytCPSw1lvlTOOlX9IGta5HJHMEQCIFbi8jDcDE1sj58pcguIooGJXDhvrojvbG9CbozQPCjjAiAMHojZPBJP9LzKez7pgY+rANoKRgmxXaMjlu8kl9imHwEiAgP3LT2WZjsOqZsK6w1/JzyrEajeN4hfHd3I2REq24cWk0cwRAIgD3cndVkTdhnYwEhwYaBVWJP2E9jq1+ZQjOxNx+luXPMCICrD7DdQc314Ap9EAN+SO4jCeT9mHrVA+skEUoPCAxIdAQEEIgAgdBDiqcx7V0LtGgDr8Co4bneNqt4doLQVuq7q8EAvTw0BBfFTIQIvUztmfi6js24hlhyf6dyjQPvgr1IQFzqDrgM3qyCldiECa7U6mOgQvQ7mGg7RFkumwCR4bXZVTnk+IC3Gzpx4xOohAtW4p9ZqQf/bb0xT1hmUAi6Ia09FAB+xWLlckWTUX4yjIQMkt17q0sH5xg6K3rXnAJ/seimvzbMNgp2C0JVi/ouuhSEDLTT4kyIAgzSHvSlKohncvgALn5s9gkeZVBQwAJ8PpVEhA3Ro+OqZtsZHiDmLWtJUgMrQj0sNZb5UzjpV/SBrWuRyIQP3LT2WZjsOqZsK6w1/JzyrEajeN4hfHd3I2REq24cWk1euAQcjIgAgdBDiqcx7V0LtGgDr8Co4bneNqt4doLQVuq7q8EAvTw0BCP3MAQUARzBEAiBDugO7p7KtJGKocdosR80FvkGdE7LFEKLR3nAjqgoR5gIgVw9P3kUPCFF9d6eBvCjn5Y/YJdgVNNW6uO6CBgdH+QsBRzBEAiBW4vIw3AxNbI+fKXILiKKBiVw4b66I72xvQm6M0Dwo4wIgDB6I2TwST/S8yns+6YGPqwDaCkYJsV2jI5bvJJfYph8BRzBEAiAPdyd1WRN2GdjASHBhoFVYk/YT2OrX5lCM7E3H6W5c8wIgKsPsN1BzfXgCn0QA35I7iMJ5P2YetUD6yQRSg8IDEh0B8VMhAi9TO2Z+LqOzbiGWHJ/p3KNA++CvUhAXOoOuAzerIKV2IQJrtTqY6BC9DuYaDtEWS6bAJHhtdlVOeT4gLcbOnHjE6iEC1bin1mpB/9tvTFPWGZQCLohrT0UAH7FYuVyRZNRfjKMhAyS3XurSwfnGDoretecAn+x6Ka/Nsw2CnYLQlWL+i66FIQMtNPiTIgCDNIe9KUqiGdy+AAufmz2CR5lUFDAAnw+lUSEDdGj46pm2xkeIOYta0lSAytCPSw1lvlTOOlX9IGta5HIhA/ctPZZmOw6pmwrrDX8nPKsRqN43iF8d3cjZESrbhxaTV64AAQEgkNADAAAAAAAXqRQQjUgH6WjVFi7Jziygi6juBAJeFIciAgMkt17q0sH5xg6K3rXnAJ/seimvzbMNgp2C0JVi/ouuhUcwRAIgRYxL9S7/J5BX3SVNPySRxiXBrWTAihp3T4XxdNYz6D8CIG/e6bLrZqVUdQOtAbA41/es6Vy1hPIN6VAzFs9M5BVDASICA3Ro+OqZtsZHiDmLWtJUgMrQj0sNZb5UzjpV/SBrWuRySDBFAiEAqBvDHFkWEXJfdqanzQJ25fUKXvjWUo/wa0otJAkBD1YCIGZhS3xBgLX/pHbmYg12ENLqGQIzIJrPPID3JFdTjq0VASICA/ctPZZmOw6pmwrrDX8nPKsRqN43iF8d3cjZESrbhxaTSDBFAiEAslYryhKy5Njn1GNJt02Zugj62aOym3AsaRZiKj8aoD8CIA5KzBNNzfhCq6YMj+odwksJC3ctNT1eF5c4ijcxK5LGAQEEIgAgdBDiqcx7V0LtGgDr8Co4bneNqt4doLQVuq7q8EAvTw0BBfFTIQIvUztmfi6js24hlhyf6dyjQPvgr1IQFzqDrgM3qyCldiECa7U6mOgQvQ7mGg7RFkumwCR4bXZVTnk+IC3Gzpx4xOohAtW4p9ZqQf/bb0xT1hmUAi6Ia09FAB+xWLlckWTUX4yjIQMkt17q0sH5xg6K3rXnAJ/seimvzbMNgp2C0JVi/ouuhSEDLTT4kyIAgzSHvSlKohncvgALn5s9gkeZVBQwAJ8PpVEhA3Ro+OqZtsZHiDmLWtJUgMrQj0sNZb5UzjpV/SBrWuRyIQP3LT2WZjsOqZsK6w1/JzyrEajeN4hfHd3I2REq24cWk1euAQcjIgAgdBDiqcx7V0LtGgDr8Co4bneNqt4doLQVuq7q8EAvTw0BCP3OAQUARzBEAiBFjEv1Lv8nkFfdJU0/JJHGJcGtZMCKGndPhfF01jPoPwIgb97psutmpVR1A60BsDjX96zpXLWE8g3pUDMWz0zkFUMBSDBFAiEAqBvDHFkWEXJfdqanzQJ25fUKXvjWUo/wa0otJAkBD1YCIGZhS3xBgLX/pHbmYg12ENLqGQIzIJrPPID3JFdTjq0VAUgwRQIhALJWK8oSsuTY59RjSbdNmboI+tmjsptwLGkWYio/GqA/AiAOSswTTc34QqumDI/qHcJLCQt3LTU9XheXOIo3MSuSxgHxUyECL1M7Zn4uo7NuIZYcn+nco0D74K9SEBc6g64DN6sgpXYhAmu1OpjoEL0O5hoO0RZLpsAkeG12VU55PiAtxs6ceMTqIQLVuKfWakH/229MU9YZlAIuiGtPRQAfsVi5XJFk1F+MoyEDJLde6tLB+cYOit615wCf7Hopr82zDYKdgtCVYv6LroUhAy00+JMiAIM0h70pSqIZ3L4AC5+bPYJHmVQUMACfD6VRIQN0aPjqmbbGR4g5i1rSVIDK0I9LDWW+VM46Vf0ga1rkciED9y09lmY7DqmbCusNfyc8qxGo3jeIXx3dyNkRKtuHFpNXrgABASBwaQMAAAAAABepFBCNSAfpaNUWLsnOLKCLqO4EAl4UhyICAyS3XurSwfnGDoretecAn+x6Ka/Nsw2CnYLQlWL+i66FRzBEAiAEujxRerGoet/VhgYMeSFCkeuE8Z42OIXGx/ofrJ50/gIgLsbE5A0dlCIXXpckf35MBn9jiLVKD6tnLy1ZIj8FVe8BIgIDdGj46pm2xkeIOYta0lSAytCPSw1lvlTOOlX9IGta5HJHMEQCIEjLH1c9Rkq0wad3KqAxlpQasFjuN2gAf+mpWiazgxsnAiBl+7+NXJt8JFc5a+JNWz1f98gIwAGNOVPFo9vQJzZGhQEiAgP3LT2WZjsOqZsK6w1/JzyrEajeN4hfHd3I2REq24cWk0cwRAIgSKyvqLBOmZLbK72dTb9LdoUw01eQCBrj7Vxjgw1KHVECIEZVa67aNKIA4evyOq2e9C7+J91KkTA8GJst2eRUVskSAQEEIgAgdBDiqcx7V0LtGgDr8Co4bneNqt4doLQVuq7q8EAvTw0BBfFTIQIvUztmfi6js24hlhyf6dyjQPvgr1IQFzqDrgM3qyCldiECa7U6mOgQvQ7mGg7RFkumwCR4bXZVTnk+IC3Gzpx4xOohAtW4p9ZqQf/bb0xT1hmUAi6Ia09FAB+xWLlckWTUX4yjIQMkt17q0sH5xg6K3rXnAJ/seimvzbMNgp2C0JVi/ouuhSEDLTT4kyIAgzSHvSlKohncvgALn5s9gkeZVBQwAJ8PpVEhA3Ro+OqZtsZHiDmLWtJUgMrQj0sNZb5UzjpV/SBrWuRyIQP3LT2WZjsOqZsK6w1/JzyrEajeN4hfHd3I2REq24cWk1euAQcjIgAgdBDiqcx7V0LtGgDr8Co4bneNqt4doLQVuq7q8EAvTw0BCP3MAQUARzBEAiAEujxRerGoet/VhgYMeSFCkeuE8Z42OIXGx/ofrJ50/gIgLsbE5A0dlCIXXpckf35MBn9jiLVKD6tnLy1ZIj8FVe8BRzBEAiBIyx9XPUZKtMGndyqgMZaUGrBY7jdoAH/pqVoms4MbJwIgZfu/jVybfCRXOWviTVs9X/fICMABjTlTxaPb0Cc2RoUBRzBEAiBIrK+osE6ZktsrvZ1Nv0t2hTDTV5AIGuPtXGODDUodUQIgRlVrrto0ogDh6/I6rZ70Lv4n3UqRMDwYmy3Z5FRWyRIB8VMhAi9TO2Z+LqOzbiGWHJ/p3KNA++CvUhAXOoOuAzerIKV2IQJrtTqY6BC9DuYaDtEWS6bAJHhtdlVOeT4gLcbOnHjE6iEC1bin1mpB/9tvTFPWGZQCLohrT0UAH7FYuVyRZNRfjKMhAyS3XurSwfnGDoretecAn+x6Ka/Nsw2CnYLQlWL+i66FIQMtNPiTIgCDNIe9KUqiGdy+AAufmz2CR5lUFDAAnw+lUSEDdGj46pm2xkeIOYta0lSAytCPSw1lvlTOOlX9IGta5HIhA/ctPZZmOw6pmwrrDX8nPKsRqN43iF8d3cjZESrbhxaTV64AAA==".to_owned(),
+            })
+            .to_request();
+        let resp = app.call(req).await?;
+
+        assert_eq!(resp.status(), http::StatusCode::OK);
+
+        let response_body = to_bytes(resp.into_body()).await?;
+        let resp: serde_json::Value = serde_json::from_slice(&response_body)?;
+        assert_eq!(resp["error"], "NonSpendableInput(1)");
+        assert!(resp["permalink"].as_str().unwrap().starts_with("/v/"));
+        //assert_eq!(to_bytes(response_body).await?, r##"Hello world!"##);
+
+        Ok(())
+    }
+
+    #[actix_web::test]
+    async fn test_stream_proof() -> Result<(), Error> {
+        let app = App::new()
+            .app_data(web::Data::new(Storage::open(None).unwrap()))
+            .route("/proof/stream", web::post().to(stream_proof));
+        let app = test::init_service(app).await;
+
+        let req = test::TestRequest::post()
+            .uri("/proof/stream")
+            .set_json(ProofOfReserves {
+                addresses: vec!["2Mtkk3kjyN8hgdGXPuJCNnwS3BBY4K2frhY".to_owned()],
+                message: "message".to_owned(),
+                claimed_amount: None,
+                confirmations: None,
+                proof_psbt: "not base64".to_owned(),
+            })
+            .to_request();
+        let resp = app.call(req).await?;
+        assert_eq!(
+            resp.headers().get(http::header::CONTENT_TYPE).unwrap(),
+            "text/event-stream"
+        );
+
+        let body = to_bytes(resp.into_body()).await?;
+        let body = std::str::from_utf8(&body).unwrap();
+        let (event, data) = body
+            .strip_suffix("\n\n")
+            .and_then(|event| event.split_once('\n'))
+            .unwrap();
+        assert_eq!(event, "event: result");
+        let result: serde_json::Value = serde_json::from_str(data.strip_prefix("data: ").unwrap())?;
+        assert!(result["error"]
+            .as_str()
+            .unwrap()
+            .starts_with("Base64 decode error"));
+
+        Ok(())
+    }
+
+    #[actix_web::test]
+    async fn test_rate_limit() -> Result<(), Error> {
+        let app = App::new()
+            .app_data(web::Data::new(Storage::open(None).unwrap()))
+            .app_data(web::Data::new(RateLimiter::new(2, 1)))
+            .app_data(web::Data::new(ApiKeys::new(vec![auth::ApiKey {
+                name: "client".to_owned(),
+                role: auth::Role::Verify,
+                sha256: sha256::Hash::hash(b"key").to_string(),
+            }])))
+            .route("/proof", web::post().to(check_proof))
+            .route("/diff", web::post().to(diff_proofs));
+        let app = test::init_service(app).await;
+        let proof = ProofOfReserves {
+            addresses: vec!["2Mtkk3kjyN8hgdGXPuJCNnwS3BBY4K2frhY".to_owned()],
+            message: "message".to_owned(),
+            claimed_amount: None,
+            confirmations: None,
+            proof_psbt: "not base64".to_owned(),
+        };
+
+        let req = test::TestRequest::post()
+            .uri("/proof")
+            .set_json(&proof)
+            .to_request();
+        let resp = app.call(req).await?;
+        assert_eq!(resp.status(), http::StatusCode::OK);
+
+        let req = test::TestRequest::post()
+            .uri("/proof")
+            .set_json(&proof)
+            .to_request();
+        let resp = app.call(req).await?;
+        assert_eq!(resp.status(), http::StatusCode::TOO_MANY_REQUESTS);
+        assert_eq!(resp.headers().get("Retry-After").unwrap(), "120");
+
+        // unknown keys share the limit of the IP address
+        let req = test::TestRequest::post()
+            .uri("/proof")
+            .insert_header(("X-Api-Key", "unknown"))
+            .set_json(&proof)
+            .to_request();
+        let resp = app.call(req).await?;
+        assert_eq!(resp.status(), http::StatusCode::TOO_MANY_REQUESTS);
+
+        // clients with a configured API key have their own limit
+        let req = test::TestRequest::post()
+            .uri("/proof")
+            .insert_header(("X-Api-Key", "key"))
+            .set_json(&proof)
+            .to_request();
+        let resp = app.call(req).await?;
+        assert_eq!(resp.status(), http::StatusCode::OK);
+
+        // so do the other verifying endpoints
+        let req = test::TestRequest::post()
+            .uri("/diff")
+            .set_json(serde_json::json!({ "before": proof, "after": proof }))
+            .to_request();
+        let resp = app.call(req).await?;
+        assert_eq!(resp.status(), http::StatusCode::TOO_MANY_REQUESTS);
+
+        Ok(())
+    }
+
+    #[actix_web::test]
+    async fn test_diff_verifications() -> Result<(), Error> {
+        let storage = Storage::open(None).unwrap();
+        let record = |report: serde_json::Value| {
+            storage.insert_verification(&NewVerification {
+                request_hash: "hash",
+                proof_psbt: "psbt",
+                message: "message",
+                addresses: &["address".to_owned()],
+                backend: "backend",
+                report: &report,
+            })
+        };
+        let reserves = |spendable: u64| {
+            json!(Reserves {
+                spendable,
+                block_height: 800_000,
+                block_hash: Hash::all_zeros(),
+                addresses: vec!["address".to_owned()],
+                utxos: Vec::new(),
+                cached: false,
+            })
+        };
+        let before = record(reserves(100)).unwrap();
+        let after = record(reserves(150)).unwrap();
+        let failed = record(json!({ "error": "NonSpendableInput(1)" })).unwrap();
+        let app = App::new()
+            .app_data(web::Data::new(storage))
+            .route("/diff", web::post().to(diff_proofs));
+        let app = test::init_service(app).await;
+
+        let req = test::TestRequest::post()
+            .uri("/diff")
+            .set_json(json!({
+                "before": { "verification_id": before },
+                "after": { "verification_id": after },
+            }))
+            .to_request();
+        let resp: serde_json::Value = test::call_and_read_body_json(&app, req).await;
+        assert_eq!(resp["net_change"], 50);
+
+        // results only come from the history
+        let req = test::TestRequest::post()
+            .uri("/diff")
+            .set_json(json!({ "before": reserves(0), "after": { "verification_id": after } }))
+            .to_request();
+        let resp = app.call(req).await?;
+        assert_eq!(resp.status(), http::StatusCode::BAD_REQUEST);
+
+        let req = test::TestRequest::post()
+            .uri("/diff")
+            .set_json(json!({
+                "before": { "verification_id": failed },
+                "after": { "verification_id": after },
+            }))
+            .to_request();
+        let resp: serde_json::Value = test::call_and_read_body_json(&app, req).await;
+        assert!(resp["error"]
+            .as_str()
+            .unwrap()
+            .starts_with("before: Verification 3 failed"));
+
+        Ok(())
+    }
+
+    #[actix_web::test]
+    async fn test_log() -> Result<(), Error> {
+        let storage = web::Data::new(Storage::open(None).unwrap());
+        let app = App::new()
+            .app_data(storage.clone())
+            .app_data(web::Data::new(Mutex::new(Tree::default())))
+            .route("/log/sth", web::get().to(log_tree_head))
+            .route("/log/entries", web::get().to(log_entries))
+            .route("/log/inclusion", web::get().to(log_inclusion))
+            .route("/log/consistency", web::get().to(log_consistency));
+        let app = test::init_service(app).await;
+
+        let req = test::TestRequest::get()
+            .uri(&format!("/log/entries?start={}", i64::MAX))
+            .to_request();
+        let resp: serde_json::Value = test::call_and_read_body_json(&app, req).await;
+        assert_eq!(resp, json!([]));
+
+        let req = test::TestRequest::get()
+            .uri("/log/entries?start=-1")
+            .to_request();
+        let resp: serde_json::Value = test::call_and_read_body_json(&app, req).await;
+        assert_eq!(resp["error"], "Invalid start -1");
+
+        // tree heads are only served signed
+        let req = test::TestRequest::get().uri("/log/sth").to_request();
+        let resp = app.call(req).await?;
+        assert_eq!(resp.status(), http::StatusCode::NOT_FOUND);
+
+        let append = |entry: &str| {
+            let leaf_hash = transparency::leaf_hash(entry.as_bytes()).to_string();
+            storage.append_log_entry(&leaf_hash, entry).unwrap();
+        };
+        append("first");
+        append("second");
+        let req = test::TestRequest::get()
+            .uri("/log/inclusion?index=1")
+            .to_request();
+        let resp: serde_json::Value = test::call_and_read_body_json(&app, req).await;
+        assert_eq!(resp["tree_size"], 2);
+        assert_eq!(resp["audit_path"].as_array().unwrap().len(), 1);
+
+        // the tree follows the entries appended since
+        append("third");
+        let req = test::TestRequest::get()
+            .uri("/log/consistency?first=2&second=3")
+            .to_request();
+        let resp: serde_json::Value = test::call_and_read_body_json(&app, req).await;
+        assert_eq!(resp["consistency"].as_array().unwrap().len(), 1);
+        let req = test::TestRequest::get()
+            .uri("/log/inclusion?index=2&tree_size=4")
+            .to_request();
+        let resp: serde_json::Value = test::call_and_read_body_json(&app, req).await;
+        assert_eq!(resp["error"], "Leaf 2 is not part of a tree of size 4");
+
+        Ok(())
+    }
+
+    #[actix_web::test]
+    async fn test_health() -> Result<(), Error> {
+        let app = App::new()
+            .app_data(web::Data::new(Storage::open(None).unwrap()))
+            .app_data(web::Data::new(
+                ReservesVerifier::from_config(
+                    &Config {
+                        backends: [(
+                            bdk_wallet::bitcoin::Network::Regtest,
+                            "tcp://127.0.0.1:1".to_owned(),
+                        )]
+                        .into(),
+                        ..Default::default()
+                    },
+                    None,
+                )
+                .unwrap(),
+            ))
+            .route("/healthz", web::get().to(healthz))
+            .route("/readyz", web::get().to(readyz));
+        let app = test::init_service(app).await;
+
+        let req = test::TestRequest::get().uri("/healthz").to_request();
+        let resp = app.call(req).await?;
+        assert_eq!(resp.status(), http::StatusCode::OK);
+
+        // nothing listens on port 1
+        let req = test::TestRequest::get().uri("/readyz").to_request();
+        let resp = app.call(req).await?;
+        assert_eq!(resp.status(), http::StatusCode::SERVICE_UNAVAILABLE);
+        let body: serde_json::Value = serde_json::from_slice(&to_bytes(resp.into_body()).await?)?;
+        assert_eq!(body["storage"]["ready"], true);
+        assert_eq!(body["backends"][0]["network"], "regtest");
+        assert!(body["backends"][0]["error"]
+            .as_str()
+            .unwrap()
+            .starts_with("Failed to create Electrum client"));
+
+        Ok(())
+    }
+
+    #[actix_web::test]
+    async fn test_unsigned_proof() -> Result<(), Error> {
+        let app = App::new().route("/proof/unsigned", web::post().to(unsigned_proof));
+        let app = test::init_service(app).await;
+
+        // private keys must stay with the custodian
+        let req = test::TestRequest::post()
+            .uri("/proof/unsigned")
+            .set_json(serde_json::json!({
+                "descriptor": "wpkh(tprv8ZgxMBicQKsPcx5nBGsR63Pe8KnRUqmbJNENAfGftF3yuXoMMoVJJcYeUw5eVkm9WBPjWYt6HMWYJNesB5HaNVBaFc1M6dRjWSYnmewUMYy/84h/1h/0h/0/*)",
+                "message": "message",
+                "network": "testnet",
+            }))
+            .to_request();
+        let resp: serde_json::Value = test::call_and_read_body_json(&app, req).await;
+        assert_eq!(
+            resp["error"],
+            "Invalid watch-only descriptor, only public keys are accepted"
+        );
+
+        Ok(())
+    }
+
+    #[actix_web::test]
+    async fn test_inclusion() -> Result<(), Error> {
+        let app = App::new().route("/inclusion", web::post().to(check_inclusion));
+        let app = test::init_service(app).await;
+
+        let leaf = crate::liabilities::Leaf {
+            hashed_id: "aa".repeat(32),
+            balance: 10,
+            salt: "00ff".to_owned(),
+        };
+        let root = leaf.node().unwrap();
+        let req = test::TestRequest::post()
+            .uri("/inclusion")
+            .set_json(serde_json::json!({ "root": root, "leaf": leaf, "path": [] }))
+            .to_request();
+        let resp: serde_json::Value = test::call_and_read_body_json(&app, req).await;
+        assert_eq!(resp["included"], true);
+        assert_eq!(resp["balance"], 10);
+
+        let req = test::TestRequest::post()
+            .uri("/inclusion")
+            .set_json(serde_json::json!({
+                "root": { "hash": root.hash, "sum": 11 },
+                "leaf": leaf,
+                "path": [],
+            }))
+            .to_request();
+        let resp: serde_json::Value = test::call_and_read_body_json(&app, req).await;
+        assert_eq!(resp["included"], false);
+
+        Ok(())
+    }
+
+    #[actix_web::test]
+    async fn test_load_api_keys() {
+        let path =
+            std::env::temp_dir().join(format!("bdk-reserves-web-keys-{}.toml", std::process::id()));
+        fs::write(&path, "# no keys yet\n").unwrap();
+        let mut config = Config::default();
+        config.auth.keys_file = Some(path.to_string_lossy().into_owned());
+        assert!(load_api_keys(&config).unwrap().is_none());
+
+        // an empty keys file does not open the monitors to everyone
+        config.features.monitors = true;
+        assert_eq!(
+            load_api_keys(&config).err(),
+            Some("Monitors and webhooks require API keys, but none were loaded".to_string())
+        );
+        fs::write(
+            &path,
+            format!(
+                "[[keys]]\nname = \"ops\"\nrole = \"monitor-manager\"\nsha256 = \"{}\"\n",
+                "00".repeat(32)
+            ),
+        )
+        .unwrap();
+        let keys = load_api_keys(&config).unwrap();
+        assert_eq!(keys.map(|keys| keys.len()), Some(1));
+        fs::remove_file(path).unwrap();
+    }
+}
@@ -0,0 +1,1025 @@
+//! Persistent state in an embedded SQLite database: the history of verifications, permalinks,
+//! the transparency log, the registered monitors and webhooks, and the verification jobs.
+
+use crate::ProvenUtxo;
+use bdk_wallet::bitcoin::OutPoint;
+use rusqlite::{params, params_from_iter, types::Value, Connection, OptionalExtension, Row};
+use serde::{Deserialize, Serialize};
+use std::{
+    sync::Mutex,
+    time::{SystemTime, UNIX_EPOCH},
+};
+
+/// Rows an in-memory database keeps of the history, the jobs and the transparency log, which
+/// would grow with every request otherwise.
+const MEMORY_MAX_ROWS: i64 = 1_000;
+
+pub struct Storage {
+    conn: Mutex<Connection>,
+    /// The rows kept per table, the oldest are dropped beyond them, unlimited in a file.
+    max_rows: Option<i64>,
+}
+
+/// A verification to be added to the history.
+pub struct NewVerification<'a> {
+    pub request_hash: &'a str,
+    pub proof_psbt: &'a str,
+    pub message: &'a str,
+    pub addresses: &'a [String],
+    pub backend: &'a str,
+    /// The response sent to the client, either a verification result or an error.
+    pub report: &'a serde_json::Value,
+}
+
+/// A verification from the history.
+#[derive(Debug, Serialize)]
+pub struct VerificationRecord {
+    pub id: i64,
+    pub request_hash: String,
+    /// Only filled in when fetching a single record.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub proof_psbt: Option<String>,
+    pub message: String,
+    pub addresses: Vec<String>,
+    pub anchor_height: Option<i64>,
+    pub anchor_hash: Option<String>,
+    pub outcome: String,
+    pub report: serde_json::Value,
+    pub backend: String,
+    /// Unix timestamp in seconds.
+    pub created_at: i64,
+}
+
+/// An entry of the transparency log.
+#[derive(Debug, Serialize)]
+pub struct LogEntry {
+    pub index: i64,
+    pub leaf_hash: String,
+    /// The logged data, the leaf hash commits to its exact bytes.
+    pub entry: String,
+    /// Unix timestamp in seconds.
+    pub created_at: i64,
+}
+
+/// A proof which is re-verified periodically, together with the state of the last check.
+#[derive(Debug, Clone, Serialize)]
+pub struct MonitorRecord {
+    pub id: i64,
+    #[serde(skip)]
+    pub proof_psbt: String,
+    pub message: String,
+    pub addresses: Vec<String>,
+    /// Alert when the spendable amount falls below this many sats.
+    pub floor: Option<u64>,
+    /// Seconds between checks, or on every new block if there is none.
+    pub interval: Option<u64>,
+    /// One of "pending", "ok" or "failing".
+    pub status: String,
+    /// The spendable amount of the last check, only counting proven utxos which are still unspent.
+    pub spendable: Option<u64>,
+    pub proven_utxos: Vec<ProvenUtxo>,
+    pub spent_outpoints: Vec<OutPoint>,
+    pub below_floor: bool,
+    pub last_block_height: Option<i64>,
+    /// Unix timestamp in seconds.
+    pub last_checked_at: Option<i64>,
+    /// Unix timestamp in seconds.
+    pub created_at: i64,
+}
+
+/// Something a monitor noticed, like a spent utxo or reserves below the floor.
+#[derive(Debug, Serialize)]
+pub struct MonitorEvent {
+    pub id: i64,
+    pub monitor_id: i64,
+    pub kind: String,
+    pub detail: serde_json::Value,
+    /// Unix timestamp in seconds.
+    pub created_at: i64,
+}
+
+/// An outgoing webhook.
+#[derive(Debug, Clone, Serialize)]
+pub struct WebhookRecord {
+    pub id: i64,
+    pub url: String,
+    /// The key of the HMAC signature, never returned by the API.
+    #[serde(skip)]
+    pub secret: String,
+    /// The events the webhook is subscribed to, all events if empty.
+    pub events: Vec<String>,
+    /// Unix timestamp in seconds.
+    pub created_at: i64,
+}
+
+/// An attempt to deliver an event to a webhook.
+#[derive(Debug, Serialize)]
+pub struct WebhookDelivery {
+    pub id: i64,
+    pub webhook_id: i64,
+    pub event: String,
+    pub payload: String,
+    /// One of "pending", "delivered" or "failed".
+    pub status: String,
+    pub attempts: u32,
+    pub response_status: Option<u16>,
+    pub error: Option<String>,
+    /// Unix timestamp in seconds.
+    pub created_at: i64,
+    /// Unix timestamp in seconds.
+    pub updated_at: i64,
+}
+
+/// An asynchronous verification of a proof of reserves.
+#[derive(Debug, Serialize)]
+pub struct JobRecord {
+    pub id: i64,
+    /// The submitted proof as JSON.
+    #[serde(skip)]
+    pub request: String,
+    /// One of "queued", "running", "completed" or "failed".
+    pub status: String,
+    /// The last step of the verification while it is running.
+    pub progress: Option<serde_json::Value>,
+    /// The answer of the verification, as returned by `/proof`.
+    pub result: Option<serde_json::Value>,
+    /// Unix timestamp in seconds.
+    pub created_at: i64,
+    /// Unix timestamp in seconds.
+    pub updated_at: i64,
+}
+
+/// Filters for listing the history, newest first.
+#[derive(Debug, Default, Deserialize)]
+pub struct VerificationQuery {
+    pub address: Option<String>,
+    pub request_hash: Option<String>,
+    /// Either "verified" or "failed".
+    pub outcome: Option<String>,
+    /// Unix timestamp in seconds.
+    pub since: Option<i64>,
+    /// Unix timestamp in seconds.
+    pub until: Option<i64>,
+    pub limit: Option<u32>,
+    pub offset: Option<u32>,
+}
+
+const MAX_LIMIT: u32 = 1000;
+
+impl Storage {
+    /// Open the database at the given path, or an in-memory database if there is none.
+    pub fn open(path: Option<&str>) -> Result<Self, String> {
+        let conn = match path {
+            Some(path) => Connection::open(path),
+            None => Connection::open_in_memory(),
+        }
+        .map_err(|e| format!("Failed to open database: {:?}", e))?;
+
+        conn.execute_batch(
+            "CREATE TABLE IF NOT EXISTS verifications (
+                id INTEGER PRIMARY KEY AUTOINCREMENT,
+                request_hash TEXT NOT NULL,
+                proof_psbt TEXT NOT NULL,
+                message TEXT NOT NULL,
+                addresses TEXT NOT NULL,
+                anchor_height INTEGER,
+                anchor_hash TEXT,
+                outcome TEXT NOT NULL,
+                report TEXT NOT NULL,
+                backend TEXT NOT NULL,
+                created_at INTEGER NOT NULL
+            );
+            CREATE INDEX IF NOT EXISTS verifications_request_hash
+                ON verifications (request_hash);
+            CREATE INDEX IF NOT EXISTS verifications_created_at
+                ON verifications (created_at);
+            CREATE TABLE IF NOT EXISTS permalinks (
+                id TEXT PRIMARY KEY,
+                verification_id INTEGER NOT NULL REFERENCES verifications (id)
+            );
+            CREATE TABLE IF NOT EXISTS log_entries (
+                idx INTEGER PRIMARY KEY,
+                leaf_hash TEXT NOT NULL,
+                entry TEXT NOT NULL,
+                created_at INTEGER NOT NULL
+            );
+            CREATE TABLE IF NOT EXISTS monitors (
+                id INTEGER PRIMARY KEY AUTOINCREMENT,
+                proof_psbt TEXT NOT NULL,
+                message TEXT NOT NULL,
+                addresses TEXT NOT NULL,
+                floor INTEGER,
+                interval INTEGER,
+                status TEXT NOT NULL,
+                spendable INTEGER,
+                proven_utxos TEXT NOT NULL,
+                spent_outpoints TEXT NOT NULL,
+                below_floor INTEGER NOT NULL,
+                last_block_height INTEGER,
+                last_checked_at INTEGER,
+                created_at INTEGER NOT NULL
+            );
+            CREATE TABLE IF NOT EXISTS monitor_events (
+                id INTEGER PRIMARY KEY AUTOINCREMENT,
+                monitor_id INTEGER NOT NULL,
+                kind TEXT NOT NULL,
+                detail TEXT NOT NULL,
+                created_at INTEGER NOT NULL
+            );
+            CREATE INDEX IF NOT EXISTS monitor_events_monitor_id
+                ON monitor_events (monitor_id);
+            CREATE TABLE IF NOT EXISTS webhooks (
+                id INTEGER PRIMARY KEY AUTOINCREMENT,
+                url TEXT NOT NULL,
+                secret TEXT NOT NULL,
+                events TEXT NOT NULL,
+                created_at INTEGER NOT NULL
+            );
+            CREATE TABLE IF NOT EXISTS webhook_deliveries (
+                id INTEGER PRIMARY KEY AUTOINCREMENT,
+                webhook_id INTEGER NOT NULL,
+                event TEXT NOT NULL,
+                payload TEXT NOT NULL,
+                status TEXT NOT NULL,
+                attempts INTEGER NOT NULL,
+                response_status INTEGER,
+                error TEXT,
+                created_at INTEGER NOT NULL,
+                updated_at INTEGER NOT NULL
+            );
+            CREATE INDEX IF NOT EXISTS webhook_deliveries_webhook_id
+                ON webhook_deliveries (webhook_id);
+            CREATE TABLE IF NOT EXISTS jobs (
+                id INTEGER PRIMARY KEY AUTOINCREMENT,
+                request TEXT NOT NULL,
+                status TEXT NOT NULL,
+                progress TEXT,
+                result TEXT,
+                created_at INTEGER NOT NULL,
+                updated_at INTEGER NOT NULL
+            );",
+        )
+        .map_err(|e| format!("Failed to create database schema: {:?}", e))?;
+
+        Ok(Storage {
+            conn: Mutex::new(conn),
+            max_rows: path.is_none().then_some(MEMORY_MAX_ROWS),
+        })
+    }
+
+    pub fn insert_verification(&self, verification: &NewVerification) -> Result<i64, String> {
+        let report = verification.report;
+        let outcome = if report.get("error").is_some() {
+            "failed"
+        } else {
+            "verified"
+        };
+        // the proof of a failed verification is of no use, and may be any garbage up to the
+        // payload limit
+        let proof_psbt = match outcome {
+            "failed" => "",
+            _ => verification.proof_psbt,
+        };
+        let addresses = serde_json::to_string(verification.addresses)
+            .map_err(|e| format!("Failed to serialize addresses: {:?}", e))?;
+
+        let conn = self.conn.lock().unwrap();
+        conn.execute(
+            "INSERT INTO verifications (
+                request_hash, proof_psbt, message, addresses, anchor_height, anchor_hash,
+                outcome, report, backend, created_at
+            ) VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10)",
+            params![
+                verification.request_hash,
+                proof_psbt,
+                verification.message,
+                addresses,
+                report
+                    .get("block_height")
+                    .and_then(|height| height.as_i64()),
+                report.get("block_hash").and_then(|hash| hash.as_str()),
+                outcome,
+                report.to_string(),
+                verification.backend,
+                now(),
+            ],
+        )
+        .map_err(|e| format!("Failed to store verification: {:?}", e))?;
+        let id = conn.last_insert_rowid();
+        if let Some(max_rows) = self.max_rows {
+            conn.execute_batch(&format!(
+                "DELETE FROM permalinks WHERE verification_id <= {oldest};
+                DELETE FROM verifications WHERE id <= {oldest};",
+                oldest = id - max_rows
+            ))
+            .map_err(|e| format!("Failed to drop old verifications: {:?}", e))?;
+        }
+        Ok(id)
+    }
+
+    pub fn get_verification(&self, id: i64) -> Result<Option<VerificationRecord>, String> {
+        let conn = self.conn.lock().unwrap();
+        conn.query_row(
+            "SELECT id, request_hash, message, addresses, anchor_height, anchor_hash, outcome,
+                report, backend, created_at, proof_psbt
+            FROM verifications WHERE id = ?1",
+            params![id],
+            |row| {
+                let mut record = record_from_row(row)?;
+                let proof_psbt: String = row.get(10)?;
+                record.proof_psbt = (!proof_psbt.is_empty()).then_some(proof_psbt);
+                Ok(record)
+            },
+        )
+        .optional()
+        .map_err(|e| format!("Failed to load verification: {:?}", e))
+    }
+
+    /// Save a permalink to a verification, keeping the first verification for an existing id.
+    pub fn insert_permalink(&self, id: &str, verification_id: i64) -> Result<(), String> {
+        let conn = self.conn.lock().unwrap();
+        conn.execute(
+            "INSERT OR IGNORE INTO permalinks (id, verification_id) VALUES (?1, ?2)",
+            params![id, verification_id],
+        )
+        .map_err(|e| format!("Failed to store permalink: {:?}", e))?;
+        Ok(())
+    }
+
+    pub fn get_permalink(&self, id: &str) -> Result<Option<VerificationRecord>, String> {
+        let verification_id = {
+            let conn = self.conn.lock().unwrap();
+            conn.query_row(
+                "SELECT verification_id FROM permalinks WHERE id = ?1",
+                params![id],
+                |row| row.get(0),
+            )
+            .optional()
+            .map_err(|e| format!("Failed to load permalink: {:?}", e))?
+        };
+        match verification_id {
+            Some(verification_id) => self.get_verification(verification_id),
+            None => Ok(None),
+        }
+    }
+
+    /// Append an entry to the transparency log and return its index. An append-only log cannot
+    /// drop old entries, in memory it stops growing once full.
+    pub fn append_log_entry(&self, leaf_hash: &str, entry: &str) -> Result<i64, String> {
+        let conn = self.conn.lock().unwrap();
+        if let Some(max_rows) = self.max_rows {
+            let size: i64 = conn
+                .query_row("SELECT COUNT(*) FROM log_entries", [], |row| row.get(0))
+                .map_err(|e| format!("Failed to load the transparency log: {:?}", e))?;
+            if size >= max_rows {
+                return Err(format!(
+                    "The transparency log in memory is full with {} entries, set a database path to keep more",
+                    size
+                ));
+            }
+        }
+        conn.query_row(
+            "INSERT INTO log_entries (idx, leaf_hash, entry, created_at)
+            VALUES ((SELECT COUNT(*) FROM log_entries), ?1, ?2, ?3)
+            RETURNING idx",
+            params![leaf_hash, entry, now()],
+            |row| row.get(0),
+        )
+        .map_err(|e| format!("Failed to append to the transparency log: {:?}", e))
+    }
+
+    /// The leaf hashes of the transparency log from the entry at `start` on.
+    pub fn log_leaf_hashes(&self, start: i64) -> Result<Vec<String>, String> {
+        let conn = self.conn.lock().unwrap();
+        let mut statement = conn
+            .prepare("SELECT leaf_hash FROM log_entries WHERE idx >= ?1 ORDER BY idx")
+            .map_err(|e| format!("Failed to load the transparency log: {:?}", e))?;
+        let leaf_hashes = statement
+            .query_map(params![start], |row| row.get(0))
+            .and_then(|rows| rows.collect::<Result<Vec<_>, _>>())
+            .map_err(|e| format!("Failed to load the transparency log: {:?}", e))?;
+        Ok(leaf_hashes)
+    }
+
+    /// The transparency log entries from `start` up to, but excluding, `end`.
+    pub fn log_entries(&self, start: i64, end: i64) -> Result<Vec<LogEntry>, String> {
+        let conn = self.conn.lock().unwrap();
+        let mut statement = conn
+            .prepare(
+                "SELECT idx, leaf_hash, entry, created_at FROM log_entries
+                WHERE idx >= ?1 AND idx < ?2 ORDER BY idx",
+            )
+            .map_err(|e| format!("Failed to load the transparency log: {:?}", e))?;
+        let entries = statement
+            .query_map(params![start, end], |row| {
+                Ok(LogEntry {
+                    index: row.get(0)?,
+                    leaf_hash: row.get(1)?,
+                    entry: row.get(2)?,
+                    created_at: row.get(3)?,
+                })
+            })
+            .and_then(|rows| rows.collect::<Result<Vec<_>, _>>())
+            .map_err(|e| format!("Failed to load the transparency log: {:?}", e))?;
+        Ok(entries)
+    }
+
+    pub fn insert_monitor(
+        &self,
+        proof_psbt: &str,
+        message: &str,
+        addresses: &[String],
+        floor: Option<u64>,
+        interval: Option<u64>,
+    ) -> Result<i64, String> {
+        let addresses = serde_json::to_string(addresses)
+            .map_err(|e| format!("Failed to serialize addresses: {:?}", e))?;
+        let conn = self.conn.lock().unwrap();
+        conn.execute(
+            "INSERT INTO monitors (
+                proof_psbt, message, addresses, floor, interval, status, proven_utxos,
+                spent_outpoints, below_floor, created_at
+            ) VALUES (?1, ?2, ?3, ?4, ?5, 'pending', '[]', '[]', 0, ?6)",
+            params![proof_psbt, message, addresses, floor, interval, now()],
+        )
+        .map_err(|e| format!("Failed to store monitor: {:?}", e))?;
+        Ok(conn.last_insert_rowid())
+    }
+
+    pub fn list_monitors(&self) -> Result<Vec<MonitorRecord>, String> {
+        let conn = self.conn.lock().unwrap();
+        let mut statement = conn
+            .prepare(&format!("{} ORDER BY id", SELECT_MONITORS))
+            .map_err(|e| format!("Failed to load monitors: {:?}", e))?;
+        let monitors = statement
+            .query_map([], monitor_from_row)
+            .and_then(|rows| rows.collect::<Result<Vec<_>, _>>())
+            .map_err(|e| format!("Failed to load monitors: {:?}", e))?;
+        Ok(monitors)
+    }
+
+    pub fn get_monitor(&self, id: i64) -> Result<Option<MonitorRecord>, String> {
+        let conn = self.conn.lock().unwrap();
+        conn.query_row(
+            &format!("{} WHERE id = ?1", SELECT_MONITORS),
+            params![id],
+            monitor_from_row,
+        )
+        .optional()
+        .map_err(|e| format!("Failed to load monitor: {:?}", e))
+    }
+
+    /// Delete a monitor and its events, returning whether it existed.
+    pub fn delete_monitor(&self, id: i64) -> Result<bool, String> {
+        let conn = self.conn.lock().unwrap();
+        conn.execute(
+            "DELETE FROM monitor_events WHERE monitor_id = ?1",
+            params![id],
+        )
+        .and_then(|_| conn.execute("DELETE FROM monitors WHERE id = ?1", params![id]))
+        .map(|deleted| deleted > 0)
+        .map_err(|e| format!("Failed to delete monitor: {:?}", e))
+    }
+
+    /// Save the state of the last check of a monitor.
+    pub fn update_monitor(&self, monitor: &MonitorRecord) -> Result<(), String> {
+        let proven_utxos = serde_json::to_string(&monitor.proven_utxos)
+            .map_err(|e| format!("Failed to serialize utxos: {:?}", e))?;
+        let spent_outpoints = serde_json::to_string(&monitor.spent_outpoints)
+            .map_err(|e| format!("Failed to serialize outpoints: {:?}", e))?;
+        let conn = self.conn.lock().unwrap();
+        conn.execute(
+            "UPDATE monitors SET status = ?2, spendable = ?3, proven_utxos = ?4,
+                spent_outpoints = ?5, below_floor = ?6, last_block_height = ?7,
+                last_checked_at = ?8
+            WHERE id = ?1",
+            params![
+                monitor.id,
+                monitor.status,
+                monitor.spendable,
+                proven_utxos,
+                spent_outpoints,
+                monitor.below_floor,
+                monitor.last_block_height,
+                monitor.last_checked_at,
+            ],
+        )
+        .map_err(|e| format!("Failed to update monitor: {:?}", e))?;
+        Ok(())
+    }
+
+    pub fn insert_monitor_event(
+        &self,
+        monitor_id: i64,
+        kind: &str,
+        detail: &serde_json::Value,
+    ) -> Result<i64, String> {
+        let conn = self.conn.lock().unwrap();
+        conn.execute(
+            "INSERT INTO monitor_events (monitor_id, kind, detail, created_at)
+            VALUES (?1, ?2, ?3, ?4)",
+            params![monitor_id, kind, detail.to_string(), now()],
+        )
+        .map_err(|e| format!("Failed to store monitor event: {:?}", e))?;
+        Ok(conn.last_insert_rowid())
+    }
+
+    /// The events of a monitor, newest first.
+    pub fn list_monitor_events(&self, monitor_id: i64) -> Result<Vec<MonitorEvent>, String> {
+        let conn = self.conn.lock().unwrap();
+        let mut statement = conn
+            .prepare(
+                "SELECT id, monitor_id, kind, detail, created_at FROM monitor_events
+                WHERE monitor_id = ?1 ORDER BY id DESC",
+            )
+            .map_err(|e| format!("Failed to load monitor events: {:?}", e))?;
+        let events = statement
+            .query_map(params![monitor_id], |row| {
+                let detail: String = row.get(3)?;
+                Ok(MonitorEvent {
+                    id: row.get(0)?,
+                    monitor_id: row.get(1)?,
+                    kind: row.get(2)?,
+                    detail: serde_json::from_str(&detail).unwrap_or(serde_json::Value::Null),
+                    created_at: row.get(4)?,
+                })
+            })
+            .and_then(|rows| rows.collect::<Result<Vec<_>, _>>())
+            .map_err(|e| format!("Failed to load monitor events: {:?}", e))?;
+        Ok(events)
+    }
+
+    pub fn insert_webhook(
+        &self,
+        url: &str,
+        secret: &str,
+        events: &[String],
+    ) -> Result<i64, String> {
+        let events = serde_json::to_string(events)
+            .map_err(|e| format!("Failed to serialize events: {:?}", e))?;
+        let conn = self.conn.lock().unwrap();
+        conn.execute(
+            "INSERT INTO webhooks (url, secret, events, created_at) VALUES (?1, ?2, ?3, ?4)",
+            params![url, secret, events, now()],
+        )
+        .map_err(|e| format!("Failed to store webhook: {:?}", e))?;
+        Ok(conn.last_insert_rowid())
+    }
+
+    pub fn list_webhooks(&self) -> Result<Vec<WebhookRecord>, String> {
+        let conn = self.conn.lock().unwrap();
+        let mut statement = conn
+            .prepare("SELECT id, url, secret, events, created_at FROM webhooks ORDER BY id")
+            .map_err(|e| format!("Failed to load webhooks: {:?}", e))?;
+        let webhooks = statement
+            .query_map([], |row| {
+                let events: String = row.get(3)?;
+                Ok(WebhookRecord {
+                    id: row.get(0)?,
+                    url: row.get(1)?,
+                    secret: row.get(2)?,
+                    events: serde_json::from_str(&events).unwrap_or_default(),
+                    created_at: row.get(4)?,
+                })
+            })
+            .and_then(|rows| rows.collect::<Result<Vec<_>, _>>())
+            .map_err(|e| format!("Failed to load webhooks: {:?}", e))?;
+        Ok(webhooks)
+    }
+
+    /// Delete a webhook and its delivery log, returning whether it existed.
+    pub fn delete_webhook(&self, id: i64) -> Result<bool, String> {
+        let conn = self.conn.lock().unwrap();
+        conn.execute(
+            "DELETE FROM webhook_deliveries WHERE webhook_id = ?1",
+            params![id],
+        )
+        .and_then(|_| conn.execute("DELETE FROM webhooks WHERE id = ?1", params![id]))
+        .map(|deleted| deleted > 0)
+        .map_err(|e| format!("Failed to delete webhook: {:?}", e))
+    }
+
+    pub fn insert_webhook_delivery(
+        &self,
+        webhook_id: i64,
+        event: &str,
+        payload: &str,
+    ) -> Result<i64, String> {
+        let conn = self.conn.lock().unwrap();
+        let now = now();
+        conn.execute(
+            "INSERT INTO webhook_deliveries (
+                webhook_id, event, payload, status, attempts, created_at, updated_at
+            ) VALUES (?1, ?2, ?3, 'pending', 0, ?4, ?4)",
+            params![webhook_id, event, payload, now],
+        )
+        .map_err(|e| format!("Failed to store webhook delivery: {:?}", e))?;
+        Ok(conn.last_insert_rowid())
+    }
+
+    /// Record the outcome of a delivery attempt.
+    pub fn update_webhook_delivery(
+        &self,
+        id: i64,
+        status: &str,
+        attempts: u32,
+        response_status: Option<u16>,
+        error: Option<&str>,
+    ) -> Result<(), String> {
+        let conn = self.conn.lock().unwrap();
+        conn.execute(
+            "UPDATE webhook_deliveries
+            SET status = ?2, attempts = ?3, response_status = ?4, error = ?5, updated_at = ?6
+            WHERE id = ?1",
+            params![id, status, attempts, response_status, error, now()],
+        )
+        .map_err(|e| format!("Failed to update webhook delivery: {:?}", e))?;
+        Ok(())
+    }
+
+    /// The delivery log of a webhook, newest first.
+    pub fn list_webhook_deliveries(&self, webhook_id: i64) -> Result<Vec<WebhookDelivery>, String> {
+        let conn = self.conn.lock().unwrap();
+        let mut statement = conn
+            .prepare(
+                "SELECT id, webhook_id, event, payload, status, attempts, response_status, error,
+                    created_at, updated_at
+                FROM webhook_deliveries WHERE webhook_id = ?1 ORDER BY id DESC LIMIT 100",
+            )
+            .map_err(|e| format!("Failed to load webhook deliveries: {:?}", e))?;
+        let deliveries = statement
+            .query_map(params![webhook_id], |row| {
+                Ok(WebhookDelivery {
+                    id: row.get(0)?,
+                    webhook_id: row.get(1)?,
+                    event: row.get(2)?,
+                    payload: row.get(3)?,
+                    status: row.get(4)?,
+                    attempts: row.get(5)?,
+                    response_status: row.get(6)?,
+                    error: row.get(7)?,
+                    created_at: row.get(8)?,
+                    updated_at: row.get(9)?,
+                })
+            })
+            .and_then(|rows| rows.collect::<Result<Vec<_>, _>>())
+            .map_err(|e| format!("Failed to load webhook deliveries: {:?}", e))?;
+        Ok(deliveries)
+    }
+
+    pub fn insert_job(&self, request: &str) -> Result<i64, String> {
+        let conn = self.conn.lock().unwrap();
+        let now = now();
+        conn.execute(
+            "INSERT INTO jobs (request, status, created_at, updated_at)
+            VALUES (?1, 'queued', ?2, ?2)",
+            params![request, now],
+        )
+        .map_err(|e| format!("Failed to store job: {:?}", e))?;
+        let id = conn.last_insert_rowid();
+        if let Some(max_rows) = self.max_rows {
+            // the workers skip a queued job which was dropped
+            conn.execute("DELETE FROM jobs WHERE id <= ?1", params![id - max_rows])
+                .map_err(|e| format!("Failed to drop old jobs: {:?}", e))?;
+        }
+        Ok(id)
+    }
+
+    pub fn get_job(&self, id: i64) -> Result<Option<JobRecord>, String> {
+        let conn = self.conn.lock().unwrap();
+        conn.query_row(
+            "SELECT id, request, status, progress, result, created_at, updated_at
+            FROM jobs WHERE id = ?1",
+            params![id],
+            |row| {
+                let progress: Option<String> = row.get(3)?;
+                let result: Option<String> = row.get(4)?;
+                Ok(JobRecord {
+                    id: row.get(0)?,
+                    request: row.get(1)?,
+                    status: row.get(2)?,
+                    progress: progress.and_then(|progress| serde_json::from_str(&progress).ok()),
+                    result: result.and_then(|result| serde_json::from_str(&result).ok()),
+                    created_at: row.get(5)?,
+                    updated_at: row.get(6)?,
+                })
+            },
+        )
+        .optional()
+        .map_err(|e| format!("Failed to load job: {:?}", e))
+    }
+
+    /// The ids of the jobs which are queued or were running, oldest first.
+    pub fn unfinished_jobs(&self) -> Result<Vec<i64>, String> {
+        let conn = self.conn.lock().unwrap();
+        let mut statement = conn
+            .prepare("SELECT id FROM jobs WHERE status IN ('queued', 'running') ORDER BY id")
+            .map_err(|e| format!("Failed to load jobs: {:?}", e))?;
+        let ids = statement
+            .query_map([], |row| row.get(0))
+            .and_then(|rows| rows.collect::<Result<Vec<_>, _>>())
+            .map_err(|e| format!("Failed to load jobs: {:?}", e))?;
+        Ok(ids)
+    }
+
+    /// The number of jobs per status.
+    pub fn count_jobs(&self) -> Result<Vec<(String, i64)>, String> {
+        let conn = self.conn.lock().unwrap();
+        let mut statement = conn
+            .prepare("SELECT status, COUNT(*) FROM jobs GROUP BY status")
+            .map_err(|e| format!("Failed to count jobs: {:?}", e))?;
+        let counts = statement
+            .query_map([], |row| Ok((row.get(0)?, row.get(1)?)))
+            .and_then(|rows| rows.collect::<Result<Vec<_>, _>>())
+            .map_err(|e| format!("Failed to count jobs: {:?}", e))?;
+        Ok(counts)
+    }
+
+    /// Record the status of a job, keeping its progress and result unless new ones are given.
+    pub fn update_job(
+        &self,
+        id: i64,
+        status: &str,
+        progress: Option<&serde_json::Value>,
+        result: Option<&serde_json::Value>,
+    ) -> Result<(), String> {
+        let conn = self.conn.lock().unwrap();
+        conn.execute(
+            "UPDATE jobs SET status = ?2, progress = COALESCE(?3, progress),
+                result = COALESCE(?4, result), updated_at = ?5
+            WHERE id = ?1",
+            params![
+                id,
+                status,
+                progress.map(|progress| progress.to_string()),
+                result.map(|result| result.to_string()),
+                now()
+            ],
+        )
+        .map_err(|e| format!("Failed to update job: {:?}", e))?;
+        Ok(())
+    }
+
+    pub fn list_verifications(
+        &self,
+        query: &VerificationQuery,
+    ) -> Result<Vec<VerificationRecord>, String> {
+        let mut sql = "SELECT id, request_hash, message, addresses, anchor_height, anchor_hash,
+                outcome, report, backend, created_at
+            FROM verifications WHERE 1 = 1"
+            .to_string();
+        let mut values = Vec::<Value>::new();
+        if let Some(address) = &query.address {
+            sql.push_str(
+                " AND EXISTS (SELECT 1 FROM json_each(verifications.addresses) WHERE value = ?)",
+            );
+            values.push(Value::Text(address.clone()));
+        }
+        if let Some(request_hash) = &query.request_hash {
+            sql.push_str(" AND request_hash = ?");
+            values.push(Value::Text(request_hash.clone()));
+        }
+        if let Some(outcome) = &query.outcome {
+            sql.push_str(" AND outcome = ?");
+            values.push(Value::Text(outcome.clone()));
+        }
+        if let Some(since) = query.since {
+            sql.push_str(" AND created_at >= ?");
+            values.push(Value::Integer(since));
+        }
+        if let Some(until) = query.until {
+            sql.push_str(" AND created_at <= ?");
+            values.push(Value::Integer(until));
+        }
+        sql.push_str(" ORDER BY id DESC LIMIT ? OFFSET ?");
+        values.push(Value::Integer(
+            query.limit.unwrap_or(100).min(MAX_LIMIT).into(),
+        ));
+        values.push(Value::Integer(query.offset.unwrap_or(0).into()));
+
+        let conn = self.conn.lock().unwrap();
+        let mut statement = conn
+            .prepare(&sql)
+            .map_err(|e| format!("Failed to query verifications: {:?}", e))?;
+        let records = statement
+            .query_map(params_from_iter(values), record_from_row)
+            .and_then(|rows| rows.collect::<Result<Vec<_>, _>>())
+            .map_err(|e| format!("Failed to query verifications: {:?}", e))?;
+        Ok(records)
+    }
+}
+
+const SELECT_MONITORS: &str = "SELECT id, proof_psbt, message, addresses, floor, interval, status,
+        spendable, proven_utxos, spent_outpoints, below_floor, last_block_height, last_checked_at,
+        created_at
+    FROM monitors";
+
+fn monitor_from_row(row: &Row) -> rusqlite::Result<MonitorRecord> {
+    let addresses: String = row.get(3)?;
+    let proven_utxos: String = row.get(8)?;
+    let spent_outpoints: String = row.get(9)?;
+    Ok(MonitorRecord {
+        id: row.get(0)?,
+        proof_psbt: row.get(1)?,
+        message: row.get(2)?,
+        addresses: serde_json::from_str(&addresses).unwrap_or_default(),
+        floor: row.get(4)?,
+        interval: row.get(5)?,
+        status: row.get(6)?,
+        spendable: row.get(7)?,
+        proven_utxos: serde_json::from_str(&proven_utxos).unwrap_or_default(),
+        spent_outpoints: serde_json::from_str(&spent_outpoints).unwrap_or_default(),
+        below_floor: row.get(10)?,
+        last_block_height: row.get(11)?,
+        last_checked_at: row.get(12)?,
+        created_at: row.get(13)?,
+    })
+}
+
+fn record_from_row(row: &Row) -> rusqlite::Result<VerificationRecord> {
+    let addresses: String = row.get(3)?;
+    let report: String = row.get(7)?;
+    Ok(VerificationRecord {
+        id: row.get(0)?,
+        request_hash: row.get(1)?,
+        proof_psbt: None,
+        message: row.get(2)?,
+        addresses: serde_json::from_str(&addresses).unwrap_or_default(),
+        anchor_height: row.get(4)?,
+        anchor_hash: row.get(5)?,
+        outcome: row.get(6)?,
+        report: serde_json::from_str(&report).unwrap_or(serde_json::Value::Null),
+        backend: row.get(8)?,
+        created_at: row.get(9)?,
+    })
+}
+
+/// The current unix timestamp in seconds.
+pub fn now() -> i64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|duration| duration.as_secs() as i64)
+        .unwrap_or_default()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_history() {
+        let storage = Storage::open(None).unwrap();
+        let addresses = vec!["addr1".to_string(), "addr2".to_string()];
+        let verified = json!({ "spendable": 10, "block_height": 100, "block_hash": "00ff" });
+        let failed = json!({ "error": "NonSpendableInput(1)" });
+
+        let id = storage
+            .insert_verification(&NewVerification {
+                request_hash: "hash1",
+                proof_psbt: "psbt",
+                message: "message",
+                addresses: &addresses,
+                backend: "ssl://electrum",
+                report: &verified,
+            })
+            .unwrap();
+        storage
+            .insert_verification(&NewVerification {
+                request_hash: "hash2",
+                proof_psbt: "psbt",
+                message: "message",
+                addresses: &addresses[1..],
+                backend: "ssl://electrum",
+                report: &failed,
+            })
+            .unwrap();
+
+        let record = storage.get_verification(id).unwrap().unwrap();
+        assert_eq!(record.proof_psbt.as_deref(), Some("psbt"));
+        assert_eq!(record.anchor_height, Some(100));
+        assert_eq!(record.anchor_hash.as_deref(), Some("00ff"));
+        assert_eq!(record.outcome, "verified");
+        assert_eq!(record.report, verified);
+        assert!(storage.get_verification(id + 10).unwrap().is_none());
+        let failure = storage.get_verification(id + 1).unwrap().unwrap();
+        assert!(failure.proof_psbt.is_none());
+
+        let all = storage
+            .list_verifications(&VerificationQuery::default())
+            .unwrap();
+        assert_eq!(all.len(), 2);
+        assert!(all[0].proof_psbt.is_none());
+
+        let query = VerificationQuery {
+            address: Some("addr1".to_string()),
+            ..Default::default()
+        };
+        assert_eq!(storage.list_verifications(&query).unwrap().len(), 1);
+
+        storage.insert_permalink("link", id).unwrap();
+        storage.insert_permalink("link", id + 1).unwrap();
+        let linked = storage.get_permalink("link").unwrap().unwrap();
+        assert_eq!(linked.id, id);
+        assert!(storage.get_permalink("unknown").unwrap().is_none());
+
+        assert_eq!(storage.append_log_entry("leaf0", "entry0"), Ok(0));
+        assert_eq!(storage.append_log_entry("leaf1", "entry1"), Ok(1));
+        assert_eq!(storage.log_leaf_hashes(0).unwrap(), vec!["leaf0", "leaf1"]);
+        assert_eq!(storage.log_leaf_hashes(1).unwrap(), vec!["leaf1"]);
+        let entries = storage.log_entries(1, 10).unwrap();
+        assert_eq!(entries.len(), 1);
+        assert_eq!(entries[0].entry, "entry1");
+
+        let query = VerificationQuery {
+            outcome: Some("failed".to_string()),
+            ..Default::default()
+        };
+        let failures = storage.list_verifications(&query).unwrap();
+        assert_eq!(failures.len(), 1);
+        assert_eq!(failures[0].request_hash, "hash2");
+    }
+
+    #[test]
+    fn test_memory_bound() {
+        let storage = Storage {
+            max_rows: Some(2),
+            ..Storage::open(None).unwrap()
+        };
+        let report = json!({ "spendable": 10 });
+        let ids = (0..3)
+            .map(|_| {
+                let id = storage
+                    .insert_verification(&NewVerification {
+                        request_hash: "hash",
+                        proof_psbt: "psbt",
+                        message: "message",
+                        addresses: &[],
+                        backend: "ssl://electrum",
+                        report: &report,
+                    })
+                    .unwrap();
+                storage.insert_permalink(&id.to_string(), id).unwrap();
+                id
+            })
+            .collect::<Vec<_>>();
+        assert!(storage.get_verification(ids[0]).unwrap().is_none());
+        assert!(storage
+            .get_permalink(&ids[0].to_string())
+            .unwrap()
+            .is_none());
+        assert!(storage.get_verification(ids[2]).unwrap().is_some());
+        let all = storage
+            .list_verifications(&VerificationQuery::default())
+            .unwrap();
+        assert_eq!(all.len(), 2);
+
+        let jobs = (0..3)
+            .map(|_| storage.insert_job("{}").unwrap())
+            .collect::<Vec<_>>();
+        assert!(storage.get_job(jobs[0]).unwrap().is_none());
+        assert!(storage.get_job(jobs[2]).unwrap().is_some());
+
+        // the log stays append-only
+        assert_eq!(storage.append_log_entry("leaf0", "entry0"), Ok(0));
+        assert_eq!(storage.append_log_entry("leaf1", "entry1"), Ok(1));
+        assert!(storage.append_log_entry("leaf2", "entry2").is_err());
+        assert_eq!(storage.log_leaf_hashes(0).unwrap().len(), 2);
+    }
+
+    #[test]
+    fn test_monitors() {
+        let storage = Storage::open(None).unwrap();
+        let addresses = vec!["addr1".to_string()];
+        let id = storage
+            .insert_monitor("psbt", "message", &addresses, Some(100), None)
+            .unwrap();
+
+        let mut monitor = storage.get_monitor(id).unwrap().unwrap();
+        assert_eq!(monitor.status, "pending");
+        assert_eq!(monitor.floor, Some(100));
+        assert!(monitor.proven_utxos.is_empty());
+
+        monitor.status = "ok".to_string();
+        monitor.spendable = Some(50);
+        monitor.below_floor = true;
+        monitor.last_block_height = Some(800_000);
+        storage.update_monitor(&monitor).unwrap();
+        let monitor = storage.get_monitor(id).unwrap().unwrap();
+        assert_eq!(monitor.status, "ok");
+        assert_eq!(monitor.spendable, Some(50));
+        assert!(monitor.below_floor);
+        assert_eq!(storage.list_monitors().unwrap().len(), 1);
+
+        storage
+            .insert_monitor_event(id, "below_floor", &serde_json::json!({ "spendable": 50 }))
+            .unwrap();
+        let events = storage.list_monitor_events(id).unwrap();
+        assert_eq!(events.len(), 1);
+        assert_eq!(events[0].detail["spendable"], 50);
+
+        assert_eq!(storage.delete_monitor(id), Ok(true));
+        assert_eq!(storage.delete_monitor(id), Ok(false));
+        assert!(storage.list_monitor_events(id).unwrap().is_empty());
+    }
+}
@@ -0,0 +1,175 @@
+//! Optional HTTPS serving with rustls, with client certificate authentication and certificate
+//! reload on SIGHUP.
+
+use rustls::{
+    crypto::ring,
+    server::{ClientHello, ResolvesServerCert, WebPkiClientVerifier},
+    sign::CertifiedKey,
+    Error, InconsistentKeys, RootCertStore, ServerConfig,
+};
+use std::{
+    fs::File,
+    io::BufReader,
+    sync::{Arc, RwLock},
+};
+
+/// The server certificate, reloaded from its files on request.
+#[derive(Debug)]
+pub struct ReloadableCert {
+    cert_path: String,
+    key_path: String,
+    current: RwLock<Arc<CertifiedKey>>,
+}
+
+impl ReloadableCert {
+    pub fn load(cert_path: &str, key_path: &str) -> Result<Self, String> {
+        Ok(ReloadableCert {
+            cert_path: cert_path.to_string(),
+            key_path: key_path.to_string(),
+            current: RwLock::new(Arc::new(load_certified_key(cert_path, key_path)?)),
+        })
+    }
+
+    /// Read the certificate and key files again, keeping the current ones if they are invalid.
+    pub fn reload(&self) -> Result<(), String> {
+        let certified_key = load_certified_key(&self.cert_path, &self.key_path)?;
+        *self.current.write().unwrap() = Arc::new(certified_key);
+        Ok(())
+    }
+}
+
+impl ResolvesServerCert for ReloadableCert {
+    fn resolve(&self, _client_hello: ClientHello) -> Option<Arc<CertifiedKey>> {
+        Some(self.current.read().unwrap().clone())
+    }
+}
+
+fn load_certified_key(cert_path: &str, key_path: &str) -> Result<CertifiedKey, String> {
+    let certs = rustls_pemfile::certs(&mut open(cert_path)?)
+        .collect::<Result<Vec<_>, _>>()
+        .map_err(|e| format!("Invalid certificate in {}: {:?}", cert_path, e))?;
+    if certs.is_empty() {
+        return Err(format!("No certificate found in {}", cert_path));
+    }
+    let key = rustls_pemfile::private_key(&mut open(key_path)?)
+        .map_err(|e| format!("Invalid private key in {}: {:?}", key_path, e))?
+        .ok_or_else(|| format!("No private key found in {}", key_path))?;
+    let signing_key = ring::sign::any_supported_type(&key)
+        .map_err(|e| format!("Unsupported private key in {}: {:?}", key_path, e))?;
+    let certified_key = CertifiedKey::new(certs, signing_key);
+    // a key of another certificate would fail every handshake
+    match certified_key.keys_match() {
+        Ok(()) | Err(Error::InconsistentKeys(InconsistentKeys::Unknown)) => Ok(certified_key),
+        Err(e) => Err(format!(
+            "The private key in {} does not match the certificate in {}: {:?}",
+            key_path, cert_path, e
+        )),
+    }
+}
+
+fn open(path: &str) -> Result<BufReader<File>, String> {
+    File::open(path)
+        .map(BufReader::new)
+        .map_err(|e| format!("Failed to open {}: {:?}", path, e))
+}
+
+/// The TLS configuration, requiring client certificates signed by the given CAs if there are any.
+pub fn server_config(
+    cert: Arc<ReloadableCert>,
+    client_ca_path: Option<&str>,
+) -> Result<ServerConfig, String> {
+    let provider = Arc::new(ring::default_provider());
+    let builder = ServerConfig::builder_with_provider(provider.clone())
+        .with_safe_default_protocol_versions()
+        .map_err(|e| format!("Invalid TLS configuration: {:?}", e))?;
+    let builder = match client_ca_path {
+        Some(path) => {
+            let mut roots = RootCertStore::empty();
+            for ca in rustls_pemfile::certs(&mut open(path)?) {
+                let ca = ca.map_err(|e| format!("Invalid certificate in {}: {:?}", path, e))?;
+                roots
+                    .add(ca)
+                    .map_err(|e| format!("Invalid client CA in {}: {:?}", path, e))?;
+            }
+            let verifier = WebPkiClientVerifier::builder_with_provider(Arc::new(roots), provider)
+                .build()
+                .map_err(|e| format!("Invalid client CAs in {}: {:?}", path, e))?;
+            builder.with_client_cert_verifier(verifier)
+        }
+        None => builder.with_no_client_auth(),
+    };
+    Ok(builder.with_cert_resolver(cert))
+}
+
+/// Reload the certificate whenever the process receives SIGHUP.
+#[cfg(unix)]
+pub fn reload_on_sighup(cert: Arc<ReloadableCert>) -> std::io::Result<()> {
+    use tokio::signal::unix::{signal, SignalKind};
+
+    let mut hangups = signal(SignalKind::hangup())?;
+    actix_web::rt::spawn(async move {
+        while hangups.recv().await.is_some() {
+            match cert.reload() {
+                Ok(()) => tracing::info!("Reloaded the TLS certificate from {}.", cert.cert_path),
+                Err(e) => tracing::error!("Failed to reload the TLS certificate: {}", e),
+            }
+        }
+    });
+    Ok(())
+}
+
+#[cfg(not(unix))]
+pub fn reload_on_sighup(_cert: Arc<ReloadableCert>) -> std::io::Result<()> {
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::{env, fs};
+
+    fn write_cert(dir: &std::path::Path, name: &str) -> (String, String) {
+        let cert = rcgen::generate_simple_self_signed(vec![name.to_string()]).unwrap();
+        let cert_path = dir.join(format!("{}.pem", name));
+        let key_path = dir.join(format!("{}.key", name));
+        fs::write(&cert_path, cert.cert.pem()).unwrap();
+        fs::write(&key_path, cert.signing_key.serialize_pem()).unwrap();
+        (
+            cert_path.to_str().unwrap().to_string(),
+            key_path.to_str().unwrap().to_string(),
+        )
+    }
+
+    #[test]
+    fn test_reload() {
+        let dir = env::temp_dir().join(format!("bdk-reserves-web-tls-{}", std::process::id()));
+        fs::create_dir_all(&dir).unwrap();
+        let (cert_path, key_path) = write_cert(&dir, "localhost");
+
+        let cert = Arc::new(ReloadableCert::load(&cert_path, &key_path).unwrap());
+        let first = cert.current.read().unwrap().cert.clone();
+        assert!(server_config(cert.clone(), None).is_ok());
+        assert!(server_config(cert.clone(), Some(&cert_path)).is_ok());
+
+        let (other_cert, other_key) = write_cert(&dir, "other");
+        fs::rename(other_cert, &cert_path).unwrap();
+        fs::rename(other_key, &key_path).unwrap();
+        cert.reload().unwrap();
+        assert_ne!(cert.current.read().unwrap().cert, first);
+
+        // the key of another certificate keeps the current one
+        let current = cert.current.read().unwrap().cert.clone();
+        let (_, mismatched_key) = write_cert(&dir, "mismatched");
+        fs::rename(mismatched_key, &key_path).unwrap();
+        assert!(cert.reload().unwrap_err().contains("does not match"));
+        assert_eq!(cert.current.read().unwrap().cert, current);
+
+        // a broken certificate keeps the current one
+        fs::write(&cert_path, "broken").unwrap();
+        assert!(cert.reload().is_err());
+        assert_ne!(cert.current.read().unwrap().cert, first);
+
+        assert!(ReloadableCert::load(&dir.join("missing").to_string_lossy(), &key_path).is_err());
+        fs::remove_dir_all(dir).unwrap();
+    }
+}
@@ -0,0 +1,311 @@
+//! Append-only Merkle log of verification results, following the tree structure of RFC 6962.
+//!
+//! Leaves are hashed as `SHA256(0x00 || entry)` and inner nodes as `SHA256(0x01 || left || right)`,
+//! so monitors can use any Certificate Transparency compatible client to check inclusion and
+//! consistency proofs.
+
+use bdk_wallet::bitcoin::hashes::{sha256, Hash, HashEngine};
+
+pub fn leaf_hash(entry: &[u8]) -> sha256::Hash {
+    let mut engine = sha256::Hash::engine();
+    engine.input(&[0x00]);
+    engine.input(entry);
+    sha256::Hash::from_engine(engine)
+}
+
+fn node_hash(left: &sha256::Hash, right: &sha256::Hash) -> sha256::Hash {
+    let mut engine = sha256::Hash::engine();
+    engine.input(&[0x01]);
+    engine.input(left.as_byte_array());
+    engine.input(right.as_byte_array());
+    sha256::Hash::from_engine(engine)
+}
+
+/// The largest power of two smaller than `n`, for `n > 1`.
+fn split(n: usize) -> usize {
+    let mut k = 1;
+    while k << 1 < n {
+        k <<= 1;
+    }
+    k
+}
+
+/// The leaf hashes of the log and the hashes of its complete subtrees, which never change once
+/// the log grew past them, so roots and proofs only hash along a path of the tree.
+#[derive(Debug, Default)]
+pub struct Tree {
+    /// `levels[l][i]` is the hash of the leaves `i << l` up to `(i + 1) << l`.
+    levels: Vec<Vec<sha256::Hash>>,
+}
+
+impl Tree {
+    pub fn len(&self) -> usize {
+        self.levels.first().map_or(0, Vec::len)
+    }
+
+    pub fn leaf(&self, index: usize) -> Option<sha256::Hash> {
+        self.levels.first()?.get(index).copied()
+    }
+
+    /// Append a leaf hash, hashing the subtrees it completes.
+    pub fn push(&mut self, leaf: sha256::Hash) {
+        let mut hash = leaf;
+        let mut level = 0;
+        loop {
+            if self.levels.len() == level {
+                self.levels.push(Vec::new());
+            }
+            let nodes = &mut self.levels[level];
+            nodes.push(hash);
+            if nodes.len() % 2 == 1 {
+                return;
+            }
+            hash = node_hash(&nodes[nodes.len() - 2], &nodes[nodes.len() - 1]);
+            level += 1;
+        }
+    }
+
+    /// The root hash of the tree of the first `size` leaves.
+    pub fn root(&self, size: usize) -> sha256::Hash {
+        self.range_root(0, size)
+    }
+
+    /// The hash of the leaves from `start` up to `end`, which RFC 6962 only asks for where
+    /// `start` is a multiple of the largest power of two up to the size.
+    fn range_root(&self, start: usize, end: usize) -> sha256::Hash {
+        let n = end - start;
+        if n == 0 {
+            return sha256::Hash::hash(&[]);
+        }
+        if n.is_power_of_two() && start & (n - 1) == 0 {
+            let level = n.trailing_zeros() as usize;
+            return self.levels[level][start >> level];
+        }
+        let k = split(n);
+        node_hash(
+            &self.range_root(start, start + k),
+            &self.range_root(start + k, end),
+        )
+    }
+
+    /// The audit path proving that leaf `index` is part of the tree of the first `size` leaves.
+    pub fn inclusion_proof(&self, index: usize, size: usize) -> Vec<sha256::Hash> {
+        self.audit_path(index, 0, size)
+    }
+
+    fn audit_path(&self, index: usize, start: usize, end: usize) -> Vec<sha256::Hash> {
+        let n = end - start;
+        if n <= 1 {
+            return Vec::new();
+        }
+        let k = split(n);
+        if index < k {
+            let mut path = self.audit_path(index, start, start + k);
+            path.push(self.range_root(start + k, end));
+            path
+        } else {
+            let mut path = self.audit_path(index - k, start + k, end);
+            path.push(self.range_root(start, start + k));
+            path
+        }
+    }
+
+    /// The proof that the tree of the first `first` leaves is a prefix of the tree of the first
+    /// `size` leaves.
+    pub fn consistency_proof(&self, first: usize, size: usize) -> Vec<sha256::Hash> {
+        if first == 0 || first >= size {
+            return Vec::new();
+        }
+        self.subproof(first, 0, size, true)
+    }
+
+    fn subproof(&self, m: usize, start: usize, end: usize, complete: bool) -> Vec<sha256::Hash> {
+        let n = end - start;
+        if m == n {
+            return if complete {
+                Vec::new()
+            } else {
+                vec![self.range_root(start, end)]
+            };
+        }
+        let k = split(n);
+        if m <= k {
+            let mut proof = self.subproof(m, start, start + k, complete);
+            proof.push(self.range_root(start + k, end));
+            proof
+        } else {
+            let mut proof = self.subproof(m - k, start + k, end, false);
+            proof.push(self.range_root(start, start + k));
+            proof
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn leaves(n: usize) -> Vec<sha256::Hash> {
+        (0..n).map(|i| leaf_hash(&[i as u8])).collect()
+    }
+
+    fn tree(leaves: &[sha256::Hash]) -> Tree {
+        let mut tree = Tree::default();
+        for leaf in leaves {
+            tree.push(*leaf);
+        }
+        tree
+    }
+
+    /// The root hash of RFC 6962, recomputed from the leaves.
+    fn root(leaves: &[sha256::Hash]) -> sha256::Hash {
+        match leaves.len() {
+            0 => sha256::Hash::hash(&[]),
+            1 => leaves[0],
+            n => {
+                let k = split(n);
+                node_hash(&root(&leaves[..k]), &root(&leaves[k..]))
+            }
+        }
+    }
+
+    /// Verify an inclusion proof as described in RFC 9162 section 2.1.3.2.
+    fn verify_inclusion(
+        index: usize,
+        size: usize,
+        leaf: sha256::Hash,
+        path: &[sha256::Hash],
+        root: sha256::Hash,
+    ) -> bool {
+        if index >= size {
+            return false;
+        }
+        let (mut fn_, mut sn, mut r) = (index, size - 1, leaf);
+        for p in path {
+            if sn == 0 {
+                return false;
+            }
+            if fn_ & 1 == 1 || fn_ == sn {
+                r = node_hash(p, &r);
+                while fn_ & 1 == 0 && fn_ != 0 {
+                    fn_ >>= 1;
+                    sn >>= 1;
+                }
+            } else {
+                r = node_hash(&r, p);
+            }
+            fn_ >>= 1;
+            sn >>= 1;
+        }
+        sn == 0 && r == root
+    }
+
+    /// Verify a consistency proof as described in RFC 9162 section 2.1.4.2.
+    fn verify_consistency(
+        first: usize,
+        second: usize,
+        first_root: sha256::Hash,
+        second_root: sha256::Hash,
+        proof: &[sha256::Hash],
+    ) -> bool {
+        if first == second {
+            return proof.is_empty() && first_root == second_root;
+        }
+        let mut proof = proof.to_vec();
+        if first.is_power_of_two() {
+            proof.insert(0, first_root);
+        }
+        let (mut fn_, mut sn) = (first - 1, second - 1);
+        while fn_ & 1 == 1 {
+            fn_ >>= 1;
+            sn >>= 1;
+        }
+        let (mut fr, mut sr) = (proof[0], proof[0]);
+        for c in &proof[1..] {
+            if sn == 0 {
+                return false;
+            }
+            if fn_ & 1 == 1 || fn_ == sn {
+                fr = node_hash(c, &fr);
+                sr = node_hash(c, &sr);
+                while fn_ & 1 == 0 && fn_ != 0 {
+                    fn_ >>= 1;
+                    sn >>= 1;
+                }
+            } else {
+                sr = node_hash(&sr, c);
+            }
+            fn_ >>= 1;
+            sn >>= 1;
+        }
+        fr == first_root && sr == second_root && sn == 0
+    }
+
+    #[test]
+    fn test_inclusion_proofs() {
+        let tree = tree(&leaves(17));
+        for size in 1..=17 {
+            let leaves = leaves(size);
+            let root = root(&leaves);
+            assert_eq!(tree.root(size), root);
+            for (index, leaf) in leaves.iter().enumerate() {
+                let path = tree.inclusion_proof(index, size);
+                assert!(verify_inclusion(index, size, *leaf, &path, root));
+                assert!(!verify_inclusion(
+                    index,
+                    size,
+                    leaf_hash(b"other"),
+                    &path,
+                    root
+                ));
+            }
+        }
+    }
+
+    #[test]
+    fn test_consistency_proofs() {
+        let tree = tree(&leaves(17));
+        for second in 1..=17 {
+            let leaves = leaves(second);
+            let second_root = root(&leaves);
+            for first in 1..=second {
+                let first_root = root(&leaves[..first]);
+                let proof = tree.consistency_proof(first, second);
+                assert!(verify_consistency(
+                    first,
+                    second,
+                    first_root,
+                    second_root,
+                    &proof
+                ));
+                if first < second {
+                    let rewritten = root(&self::leaves(first + 1)[1..]);
+                    assert!(!verify_consistency(
+                        first,
+                        second,
+                        rewritten,
+                        second_root,
+                        &proof
+                    ));
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_root() {
+        // an empty tree hashes to SHA256 of the empty string
+        assert_eq!(
+            Tree::default().root(0).to_string(),
+            "e3b0c44298fc1c149afbf4c8996fb92427ae41e4649b934ca495991b7852b855"
+        );
+        let leaves = leaves(3);
+        let tree = tree(&leaves);
+        assert_eq!(
+            tree.root(3),
+            node_hash(&node_hash(&leaves[0], &leaves[1]), &leaves[2])
+        );
+        assert_eq!(tree.leaf(2), Some(leaves[2]));
+        assert_eq!(tree.leaf(3), None);
+    }
+}
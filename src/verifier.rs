@@ -0,0 +1,695 @@
+//! Verification of proofs of reserves against the backend of their network.
+
+use crate::{
+    backend::Backend,
+    cache::{self, Cache},
+    limits::{Budget, Limits},
+    metrics::metrics,
+};
+use bdk_reserves::reserves::verify_proof;
+use bdk_wallet::bitcoin::{
+    base64::{engine::general_purpose::STANDARD, Engine},
+    psbt::Psbt,
+    Address, Amount, BlockHash, Denomination, Network, OutPoint, TxOut,
+};
+use serde::{Deserialize, Serialize};
+use std::{
+    collections::{BTreeMap, HashSet},
+    str::FromStr,
+    sync::Arc,
+};
+
+/// A proof of reserves to verify, as posted to `/proof`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ProofOfReserves {
+    pub addresses: Vec<String>,
+    pub message: String,
+    pub proof_psbt: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub claimed_amount: Option<ClaimedAmount>,
+    /// Confirmations of the reported utxos, the default of the verifier if not given.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub confirmations: Option<usize>,
+}
+
+/// The amount a custodian claims to hold: whole numbers are sats and decimal numbers are BTC,
+/// as JSON numbers or strings, or a denominated string like "1500 mBTC".
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(untagged)]
+pub enum ClaimedAmount {
+    Sats(u64),
+    Btc(f64),
+    Denominated(String),
+}
+
+impl ClaimedAmount {
+    pub fn to_sat(&self) -> Result<u64, String> {
+        match self {
+            ClaimedAmount::Sats(sats) => Ok(*sats),
+            ClaimedAmount::Btc(btc) => Amount::from_btc(*btc)
+                .map(|amount| amount.to_sat())
+                .map_err(|e| format!("Invalid claimed amount: {:?}", e)),
+            ClaimedAmount::Denominated(amount) => {
+                let amount = amount.trim();
+                if let Ok(sats) = amount.parse::<u64>() {
+                    return Ok(sats);
+                }
+                if amount.parse::<f64>().is_ok() {
+                    return Amount::from_str_in(amount, Denomination::Bitcoin)
+                        .map(|amount| amount.to_sat())
+                        .map_err(|e| format!("Invalid claimed amount: {:?}", e));
+                }
+                Amount::from_str(amount)
+                    .map(|amount| amount.to_sat())
+                    .map_err(|e| format!("Invalid claimed amount: {:?}", e))
+            }
+        }
+    }
+}
+
+/// The block up to which utxos are counted towards the reserves.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ReportingBlock {
+    /// A number of confirmations below the current tip.
+    Confirmations(usize),
+    /// A fixed block height, which must not be above the current tip.
+    Height(usize),
+}
+
+/// The confirmations of the reported utxos, requested per proof up to a maximum.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct Confirmations {
+    pub default: usize,
+    pub max: usize,
+}
+
+impl Default for Confirmations {
+    fn default() -> Self {
+        Confirmations {
+            default: 3,
+            max: 1000,
+        }
+    }
+}
+
+impl Confirmations {
+    /// The confirmations requested, or the default.
+    pub fn resolve(&self, requested: Option<usize>) -> Result<usize, String> {
+        match requested {
+            None => Ok(self.default),
+            Some(confirmations) if confirmations > self.max => Err(format!(
+                "Too many confirmations: {}, at most {} are allowed",
+                confirmations, self.max
+            )),
+            Some(confirmations) => Ok(confirmations),
+        }
+    }
+}
+
+/// The verified reserves and the block height they were reported at.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Reserves {
+    pub spendable: u64,
+    pub block_height: usize,
+    pub block_hash: BlockHash,
+    pub addresses: Vec<String>,
+    /// The utxos spent by the proof, which make up the spendable amount.
+    pub utxos: Vec<ProvenUtxo>,
+    /// Whether the result was verified before, at the same anchor block.
+    #[serde(default)]
+    pub cached: bool,
+}
+
+/// A utxo included in a proof of reserves.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ProvenUtxo {
+    pub address: String,
+    pub outpoint: OutPoint,
+    pub value: u64,
+}
+
+/// The answer to a proof of reserves: the reserves, and the solvency if an amount was claimed.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Report {
+    #[serde(flatten)]
+    pub reserves: Reserves,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub solvency: Option<Solvency>,
+}
+
+/// The claimed amount compared with the proven spendable amount.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Solvency {
+    pub claimed_amount: u64,
+    pub verdict: Verdict,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub surplus: Option<u64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub shortfall: Option<u64>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Verdict {
+    Pass,
+    Fail,
+}
+
+/// A step of a running verification.
+#[derive(Debug, Clone, Serialize)]
+#[serde(tag = "step", rename_all = "snake_case")]
+pub enum Progress {
+    PsbtParsed {
+        inputs: usize,
+    },
+    AddressChecked {
+        address: String,
+        utxos: usize,
+        checked: usize,
+        total: usize,
+    },
+    SignaturesChecked {
+        spendable: u64,
+    },
+}
+
+/// Verifies proofs of reserves against a backend per network, within limits.
+pub struct ReservesVerifier {
+    backends: BTreeMap<Network, Arc<dyn Backend>>,
+    limits: Limits,
+    confirmations: Confirmations,
+    cache: Option<Arc<Cache>>,
+}
+
+impl ReservesVerifier {
+    pub fn new(
+        backends: BTreeMap<Network, Arc<dyn Backend>>,
+        limits: Limits,
+        confirmations: Confirmations,
+        cache: Option<Arc<Cache>>,
+    ) -> Result<Self, String> {
+        if backends.is_empty() {
+            return Err("No backend configured".to_string());
+        }
+        Ok(ReservesVerifier {
+            backends,
+            limits,
+            confirmations,
+            cache,
+        })
+    }
+
+    /// A verifier over the Electrum servers, limits and confirmations of the configuration.
+    #[cfg(feature = "server")]
+    pub fn from_config(
+        config: &crate::config::Config,
+        cache: Option<Arc<Cache>>,
+    ) -> Result<Self, String> {
+        let backends = config
+            .backends
+            .iter()
+            .map(|(network, server)| {
+                let backend: Arc<dyn Backend> =
+                    Arc::new(crate::ElectrumBackend::new(server, config.health.timeout));
+                (*network, backend)
+            })
+            .collect();
+        Self::new(
+            backends,
+            config.limits.clone(),
+            config.confirmations.clone(),
+            cache,
+        )
+    }
+
+    pub fn backends(&self) -> &BTreeMap<Network, Arc<dyn Backend>> {
+        &self.backends
+    }
+
+    pub fn limits(&self) -> &Limits {
+        &self.limits
+    }
+
+    pub fn confirmations(&self) -> &Confirmations {
+        &self.confirmations
+    }
+
+    /// The backend of the first network the first address is valid for, the first backend if
+    /// there is none.
+    pub fn backend_for(
+        &self,
+        addresses: &[String],
+    ) -> Result<(&Arc<dyn Backend>, Network), String> {
+        let address = addresses
+            .first()
+            .and_then(|address| Address::from_str(address).ok());
+        let (network, backend) = self
+            .backends
+            .iter()
+            .find(|(network, _)| {
+                address
+                    .as_ref()
+                    .is_some_and(|address| address.is_valid_for_network(**network))
+            })
+            .or_else(|| self.backends.iter().next())
+            .ok_or_else(|| "No backend configured".to_string())?;
+        Ok((backend, *network))
+    }
+
+    /// Verify a proof of reserves at the requested confirmations, and its solvency if it claims
+    /// an amount.
+    pub fn verify(&self, proof: &ProofOfReserves) -> Result<Report, String> {
+        self.verify_with_progress(proof, &mut |_| {})
+    }
+
+    pub fn verify_with_progress(
+        &self,
+        proof: &ProofOfReserves,
+        on_progress: &mut dyn FnMut(Progress),
+    ) -> Result<Report, String> {
+        let claimed_amount = proof
+            .claimed_amount
+            .as_ref()
+            .map(ClaimedAmount::to_sat)
+            .transpose()?;
+        let confirmations = self.confirmations.resolve(proof.confirmations)?;
+        let reserves = self.reserves(
+            &proof.message,
+            &proof.proof_psbt,
+            ReportingBlock::Confirmations(confirmations),
+            &proof.addresses,
+            on_progress,
+        )?;
+        let solvency = claimed_amount.map(|claimed| check_solvency(claimed, reserves.spendable));
+        Ok(Report { reserves, solvency })
+    }
+
+    /// Verify the proof and count the spendable amount of the utxos confirmed at the reporting
+    /// block.
+    pub fn reserves(
+        &self,
+        message: &str,
+        psbt_base64: &str,
+        reporting_block: ReportingBlock,
+        addresses: &[String],
+        on_progress: &mut dyn FnMut(Progress),
+    ) -> Result<Reserves, String> {
+        let limits = &self.limits;
+        let cache = self.cache.as_deref();
+        let psbt = STANDARD
+            .decode(psbt_base64)
+            .map_err(|e| format!("Base64 decode error: {:?}", e))?;
+        let psbt =
+            Psbt::deserialize(&psbt).map_err(|e| format!("PSBT deserialization error: {:?}", e))?;
+        if addresses.is_empty() {
+            return Err("No address provided".to_string());
+        }
+        limits.check_addresses(addresses.len())?;
+        limits.check_inputs(psbt.unsigned_tx.input.len())?;
+        on_progress(Progress::PsbtParsed {
+            inputs: psbt.unsigned_tx.input.len(),
+        });
+        let (backend, network) = self.backend_for(addresses)?;
+        let server = backend.name();
+        let mut budget = limits.budget();
+
+        budget.spend()?;
+        let tip = backend.tip()?;
+        metrics().set_tip(network, tip.height);
+        if let Some(cache) = cache {
+            cache.update_tip(server, tip.hash);
+        }
+        let block_height = match reporting_block {
+            ReportingBlock::Confirmations(confirmations) => {
+                tip.height.checked_sub(confirmations).ok_or_else(|| {
+                    format!(
+                        "Block height is below genesis, the tip {} has fewer than {} confirmations",
+                        tip.height, confirmations
+                    )
+                })?
+            }
+            ReportingBlock::Height(height) if height <= tip.height => height,
+            ReportingBlock::Height(height) => {
+                return Err(format!(
+                    "Block height {} is above the current tip {}",
+                    height, tip.height
+                ))
+            }
+        };
+        budget.spend()?;
+        let block_hash = backend.block_hash(block_height)?;
+
+        let result_key = cache::result_key(psbt_base64, message, addresses, &block_hash);
+        if let Some(reserves) = cache.and_then(|cache| cache.result(server, &result_key)) {
+            return Ok(Reserves {
+                cached: true,
+                ..reserves
+            });
+        }
+
+        let outpoints_per_addr = addresses
+            .iter()
+            .enumerate()
+            .map(|(i, address)| {
+                let outpoints = parse_address(address, network).and_then(|address| {
+                    let script = address.script_pubkey();
+                    if let Some(utxos) =
+                        cache.and_then(|cache| cache.utxos(server, &script, &block_hash))
+                    {
+                        return Ok(utxos);
+                    }
+                    let utxos = self.confirmed_utxos(
+                        backend.as_ref(),
+                        &address,
+                        block_height,
+                        &mut budget,
+                    )?;
+                    if let Some(cache) = cache {
+                        cache.insert_utxos(server, script, block_hash, &utxos);
+                    }
+                    Ok(utxos)
+                })?;
+                on_progress(Progress::AddressChecked {
+                    address: address.clone(),
+                    utxos: outpoints.len(),
+                    checked: i + 1,
+                    total: addresses.len(),
+                });
+                Ok(outpoints)
+            })
+            .collect::<Result<Vec<Vec<_>>, String>>()?;
+
+        let proof_inputs = psbt
+            .unsigned_tx
+            .input
+            .iter()
+            .map(|input| input.previous_output)
+            .collect::<HashSet<_>>();
+        let utxos = addresses
+            .iter()
+            .zip(&outpoints_per_addr)
+            .flat_map(|(address, outpoints)| {
+                outpoints
+                    .iter()
+                    .filter(|(outpoint, _)| proof_inputs.contains(outpoint))
+                    .map(|(outpoint, txout)| ProvenUtxo {
+                        address: address.clone(),
+                        outpoint: *outpoint,
+                        value: txout.value.to_sat(),
+                    })
+            })
+            .collect();
+
+        let outpoints_combined = outpoints_per_addr.into_iter().flatten().collect();
+        let spendable =
+            verify_proof(&psbt, message, outpoints_combined).map_err(|e| format!("{:?}", e))?;
+        on_progress(Progress::SignaturesChecked { spendable });
+
+        let reserves = Reserves {
+            spendable,
+            block_height,
+            block_hash,
+            addresses: addresses.to_vec(),
+            utxos,
+            cached: false,
+        };
+        if let Some(cache) = cache {
+            cache.insert_result(server, result_key, &reserves);
+        }
+        Ok(reserves)
+    }
+
+    /// The outpoints currently unspent on any of the addresses, confirmed or not.
+    pub fn unspent_outpoints(&self, addresses: &[String]) -> Result<HashSet<OutPoint>, String> {
+        let (backend, network) = self.backend_for(addresses)?;
+        let mut outpoints = HashSet::new();
+        for address in addresses {
+            let address = parse_address(address, network)?;
+            let unspents = backend.unspent(&address.script_pubkey())?;
+            outpoints.extend(unspents.iter().map(|utxo| utxo.outpoint));
+        }
+        Ok(outpoints)
+    }
+
+    /// Fetch the utxos of an address confirmed up to the given height.
+    fn confirmed_utxos(
+        &self,
+        backend: &dyn Backend,
+        address: &Address,
+        max_confirmation_height: usize,
+        budget: &mut Budget,
+    ) -> Result<Vec<(OutPoint, TxOut)>, String> {
+        budget.spend()?;
+        let confirmed = backend
+            .unspent(&address.script_pubkey())?
+            .into_iter()
+            .filter(|utxo| utxo.height > 0 && utxo.height <= max_confirmation_height)
+            .collect::<Vec<_>>();
+        self.limits
+            .check_utxos(&address.to_string(), confirmed.len())?;
+
+        confirmed
+            .into_iter()
+            .map(|utxo| {
+                budget.spend()?;
+                let tx = backend.transaction(&utxo.outpoint.txid)?;
+                let txout = tx
+                    .output
+                    .get(utxo.outpoint.vout as usize)
+                    .cloned()
+                    .ok_or_else(|| format!("Missing output {}", utxo.outpoint))?;
+                Ok((utxo.outpoint, txout))
+            })
+            .collect()
+    }
+}
+
+fn parse_address(address: &str, network: Network) -> Result<Address, String> {
+    Address::from_str(address)
+        .map_err(|e| format!("Invalid address: {:?}", e))?
+        .require_network(network)
+        .map_err(|e| format!("Invalid address: {:?}", e))
+}
+
+/// Compare the claimed amount with the proven spendable amount.
+pub fn check_solvency(claimed: u64, spendable: u64) -> Solvency {
+    if spendable >= claimed {
+        Solvency {
+            claimed_amount: claimed,
+            verdict: Verdict::Pass,
+            surplus: Some(spendable - claimed),
+            shortfall: None,
+        }
+    } else {
+        Solvency {
+            claimed_amount: claimed,
+            verdict: Verdict::Fail,
+            surplus: None,
+            shortfall: Some(claimed - spendable),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::backend::{Tip, Unspent};
+    use bdk_wallet::bitcoin::{
+        absolute::LockTime, hashes::Hash, transaction::Version, Script, ScriptBuf, Transaction,
+        TxIn, Txid,
+    };
+    use serde_json::json;
+
+    /// A chain at height 100 where every script has the same three utxos.
+    struct FakeBackend;
+
+    impl Backend for FakeBackend {
+        fn name(&self) -> &str {
+            "fake"
+        }
+
+        fn tip(&self) -> Result<Tip, String> {
+            Ok(Tip {
+                height: 100,
+                hash: BlockHash::all_zeros(),
+                time: 0,
+            })
+        }
+
+        fn block_hash(&self, _height: usize) -> Result<BlockHash, String> {
+            Ok(BlockHash::all_zeros())
+        }
+
+        fn unspent(&self, _script: &Script) -> Result<Vec<Unspent>, String> {
+            Ok([90, 99, 0]
+                .into_iter()
+                .enumerate()
+                .map(|(vout, height)| Unspent {
+                    outpoint: OutPoint::new(Txid::all_zeros(), vout as u32),
+                    height,
+                })
+                .collect())
+        }
+
+        fn transaction(&self, _txid: &Txid) -> Result<Transaction, String> {
+            Ok(Transaction {
+                version: Version::TWO,
+                lock_time: LockTime::ZERO,
+                input: Vec::new(),
+                output: vec![TxOut {
+                    value: Amount::from_sat(1000),
+                    script_pubkey: ScriptBuf::new(),
+                }],
+            })
+        }
+    }
+
+    fn verifier(limits: Limits) -> ReservesVerifier {
+        let backend: Arc<dyn Backend> = Arc::new(FakeBackend);
+        ReservesVerifier::new(
+            [(Network::Regtest, backend)].into(),
+            limits,
+            Confirmations::default(),
+            None,
+        )
+        .unwrap()
+    }
+
+    fn proof() -> ProofOfReserves {
+        let psbt = Psbt::from_unsigned_tx(Transaction {
+            version: Version::TWO,
+            lock_time: LockTime::ZERO,
+            input: vec![TxIn {
+                previous_output: OutPoint::new(Txid::all_zeros(), 0),
+                ..Default::default()
+            }],
+            output: Vec::new(),
+        })
+        .unwrap();
+        ProofOfReserves {
+            addresses: vec!["bcrt1qw508d6qejxtdg4y5r3zarvary0c5xw7kygt080".to_string()],
+            message: "message".to_string(),
+            proof_psbt: STANDARD.encode(psbt.serialize()),
+            claimed_amount: None,
+            confirmations: None,
+        }
+    }
+
+    #[test]
+    fn test_verify() {
+        let verifier = verifier(Limits::default());
+        let mut steps = Vec::new();
+        // the proof is not signed
+        assert!(verifier
+            .verify_with_progress(&proof(), &mut |progress| steps.push(json!(progress)))
+            .is_err());
+        assert_eq!(steps[0], json!({ "step": "psbt_parsed", "inputs": 1 }));
+        // only the utxo confirmed 3 blocks below the tip counts
+        assert_eq!(steps[1]["step"], "address_checked");
+        assert_eq!(steps[1]["utxos"], 1);
+
+        assert_eq!(
+            verifier
+                .reserves(
+                    "message",
+                    &proof().proof_psbt,
+                    ReportingBlock::Height(101),
+                    &proof().addresses,
+                    &mut |_| {},
+                )
+                .unwrap_err(),
+            "Block height 101 is above the current tip 100"
+        );
+        assert_eq!(
+            verifier
+                .unspent_outpoints(&proof().addresses)
+                .unwrap()
+                .len(),
+            3
+        );
+
+        let verifier = self::verifier(Limits {
+            max_backend_calls: 3,
+            ..Default::default()
+        });
+        assert_eq!(
+            verifier.verify(&proof()).unwrap_err(),
+            "Too many backend calls, at most 3 are allowed per verification"
+        );
+        assert!(ReservesVerifier::new(
+            BTreeMap::new(),
+            Limits::default(),
+            Confirmations::default(),
+            None
+        )
+        .is_err());
+    }
+
+    #[test]
+    #[cfg(feature = "server")]
+    fn test_backend_for() {
+        use crate::config::Config;
+
+        let verifier = ReservesVerifier::from_config(&Config::default(), None).unwrap();
+        let (backend, network) = verifier
+            .backend_for(&["2Mtkk3kjyN8hgdGXPuJCNnwS3BBY4K2frhY".to_string()])
+            .unwrap();
+        assert_eq!(
+            (backend.name(), network),
+            ("ssl://electrum.blockstream.info:60002", Network::Testnet)
+        );
+
+        // an unchecked configuration is an error, not a panic
+        let config = Config {
+            backends: Default::default(),
+            ..Default::default()
+        };
+        assert!(ReservesVerifier::from_config(&config, None).is_err());
+    }
+
+    #[test]
+    fn test_claimed_amount() {
+        assert_eq!(ClaimedAmount::Sats(1234).to_sat(), Ok(1234));
+        assert_eq!(
+            ClaimedAmount::Denominated("1234".to_owned()).to_sat(),
+            Ok(1234)
+        );
+        assert_eq!(
+            ClaimedAmount::Denominated("1.5 BTC".to_owned()).to_sat(),
+            Ok(150_000_000)
+        );
+        assert!(ClaimedAmount::Denominated("lots".to_owned())
+            .to_sat()
+            .is_err());
+
+        // decimal numbers are BTC, in JSON numbers as in strings
+        let amount = |value| {
+            serde_json::from_value::<ClaimedAmount>(value)
+                .unwrap()
+                .to_sat()
+        };
+        assert_eq!(amount(json!(1234)), Ok(1234));
+        assert_eq!(amount(json!(1.5)), Ok(150_000_000));
+        assert_eq!(amount(json!(0.00000001)), Ok(1));
+        assert_eq!(amount(json!("1.5")), Ok(150_000_000));
+        assert_eq!(amount(json!(" 0.1 ")), Ok(10_000_000));
+        assert_eq!(amount(json!("1500 mBTC")), Ok(150_000_000));
+        assert!(amount(json!(-1.5)).is_err());
+        assert!(amount(json!(0.000000001)).is_err());
+        assert!(amount(json!("0.000000001")).is_err());
+        assert!(amount(json!("-1.5")).is_err());
+    }
+
+    #[test]
+    fn test_check_solvency() {
+        assert_eq!(
+            json!(check_solvency(100, 150)),
+            json!({"claimed_amount": 100, "verdict": "pass", "surplus": 50})
+        );
+        assert_eq!(
+            json!(check_solvency(150, 100)),
+            json!({"claimed_amount": 150, "verdict": "fail", "shortfall": 50})
+        );
+    }
+}
@@ -0,0 +1,386 @@
+//! Outgoing webhooks for verification and monitoring events.
+//!
+//! Every delivery is a JSON `POST` of `{"event", "timestamp", "data"}`, signed with the secret of
+//! the webhook as `X-Webhook-Signature: sha256=<hex HMAC-SHA256 of the body>`. Failed deliveries
+//! are retried with exponential back-off and every attempt is recorded in the delivery log.
+//!
+//! Deliveries are queued for a fixed number of worker threads, so a dead endpoint cannot make
+//! the server start threads without bound. Webhooks may only target public addresses, checked
+//! again on every connection, so they cannot reach the services next to the server.
+
+use crate::storage::{now, Storage, WebhookRecord};
+use bdk_wallet::bitcoin::hashes::{hmac, sha256, Hash, HashEngine};
+use serde_json::json;
+use std::{
+    io,
+    net::{IpAddr, SocketAddr, ToSocketAddrs},
+    sync::{mpsc, Arc, Mutex},
+    thread,
+    time::Duration,
+};
+use tracing::{error, Span};
+
+/// The events webhooks can subscribe to.
+pub const EVENTS: [&str; 4] = [
+    "verification_completed",
+    "verification_failed",
+    "utxo_spent",
+    "below_floor",
+];
+
+const MAX_ATTEMPTS: u32 = 5;
+/// Threads delivering webhooks, each one delivery at a time.
+const WORKERS: usize = 4;
+/// Deliveries waiting for a worker, the ones beyond fail right away.
+const QUEUE_SIZE: usize = 1_000;
+
+#[derive(Clone)]
+pub struct Webhooks {
+    storage: Arc<Storage>,
+    queue: mpsc::SyncSender<Delivery>,
+}
+
+struct Delivery {
+    webhook: WebhookRecord,
+    id: i64,
+    event: String,
+    payload: String,
+    span: Span,
+}
+
+struct Worker {
+    storage: Arc<Storage>,
+    agent: ureq::Agent,
+    /// The delay before the first retry, doubling with every further attempt.
+    backoff: Duration,
+}
+
+impl Webhooks {
+    pub fn new(storage: Arc<Storage>) -> Self {
+        let agent = ureq::AgentBuilder::new()
+            .timeout(Duration::from_secs(10))
+            .resolver(resolve_public)
+            .build();
+        Self::start(storage, agent, Duration::from_secs(1))
+    }
+
+    /// Start the workers, which stop once every clone of the webhooks is dropped.
+    fn start(storage: Arc<Storage>, agent: ureq::Agent, backoff: Duration) -> Self {
+        let (queue, receiver) = mpsc::sync_channel::<Delivery>(QUEUE_SIZE);
+        let receiver = Arc::new(Mutex::new(receiver));
+        let worker = Arc::new(Worker {
+            storage: storage.clone(),
+            agent,
+            backoff,
+        });
+        for _ in 0..WORKERS {
+            let receiver = receiver.clone();
+            let worker = worker.clone();
+            thread::spawn(move || loop {
+                let delivery = match receiver.lock().unwrap().recv() {
+                    Ok(delivery) => delivery,
+                    Err(_) => return,
+                };
+                let _span = delivery.span.enter();
+                worker.deliver(&delivery);
+            });
+        }
+        Webhooks { storage, queue }
+    }
+
+    /// Deliver the event to all webhooks subscribed to it, in the background.
+    pub fn notify(&self, event: &str, data: serde_json::Value) {
+        let webhooks = match self.storage.list_webhooks() {
+            Ok(webhooks) => webhooks,
+            Err(e) => {
+                error!("Webhooks: {}", e);
+                return;
+            }
+        };
+        let payload = json!({ "event": event, "timestamp": now(), "data": data }).to_string();
+
+        for webhook in webhooks {
+            if !webhook.events.is_empty() && !webhook.events.iter().any(|e| e == event) {
+                continue;
+            }
+            match self
+                .storage
+                .insert_webhook_delivery(webhook.id, event, &payload)
+            {
+                Ok(delivery_id) => {
+                    let delivery = Delivery {
+                        webhook,
+                        id: delivery_id,
+                        event: event.to_string(),
+                        payload: payload.clone(),
+                        span: Span::current(),
+                    };
+                    if let Err(mpsc::TrySendError::Full(delivery)) = self.queue.try_send(delivery) {
+                        self.drop_delivery(&delivery);
+                    }
+                }
+                Err(e) => error!(webhook_id = webhook.id, "{}", e),
+            }
+        }
+    }
+
+    /// Record a delivery the workers have no room for as failed.
+    fn drop_delivery(&self, delivery: &Delivery) {
+        if let Err(e) = self.storage.update_webhook_delivery(
+            delivery.id,
+            "failed",
+            0,
+            None,
+            Some("Delivery queue full"),
+        ) {
+            error!(webhook_id = delivery.webhook.id, "{}", e);
+        }
+    }
+}
+
+impl Worker {
+    fn deliver(&self, delivery: &Delivery) {
+        let Delivery {
+            webhook,
+            id: delivery_id,
+            event,
+            payload,
+            ..
+        } = delivery;
+        let delivery_id = *delivery_id;
+        let signature = format!("sha256={}", sign(&webhook.secret, payload));
+        for attempt in 1..=MAX_ATTEMPTS {
+            let result = self
+                .agent
+                .post(&webhook.url)
+                .set("Content-Type", "application/json")
+                .set("X-Webhook-Event", event)
+                .set("X-Webhook-Delivery", &delivery_id.to_string())
+                .set("X-Webhook-Signature", &signature)
+                .send_string(payload);
+            let (delivered, response_status, error) = match result {
+                Ok(response) => (true, Some(response.status()), None),
+                Err(ureq::Error::Status(status, _)) => {
+                    (false, Some(status), Some(format!("HTTP status {}", status)))
+                }
+                Err(e) => (false, None, Some(e.to_string())),
+            };
+            let status = match (delivered, attempt) {
+                (true, _) => "delivered",
+                (false, MAX_ATTEMPTS) => "failed",
+                (false, _) => "pending",
+            };
+            if let Err(e) = self.storage.update_webhook_delivery(
+                delivery_id,
+                status,
+                attempt,
+                response_status,
+                error.as_deref(),
+            ) {
+                error!(webhook_id = webhook.id, "{}", e);
+            }
+            if status != "pending" {
+                return;
+            }
+            thread::sleep(self.backoff * 2u32.pow(attempt - 1));
+        }
+    }
+}
+
+/// Check that a webhook url is http or https and only resolves to public addresses.
+pub fn check_url(url: &str) -> Result<(), String> {
+    let (rest, default_port) = if let Some(rest) = url.strip_prefix("https://") {
+        (rest, 443)
+    } else if let Some(rest) = url.strip_prefix("http://") {
+        (rest, 80)
+    } else {
+        return Err("Webhook url must be http or https".to_string());
+    };
+    let authority = rest
+        .split(['/', '?', '#'])
+        .next()
+        .unwrap_or_default()
+        .rsplit('@')
+        .next()
+        .unwrap_or_default();
+    // without a port, unless it is the last part of an IPv6 address
+    let netloc = if authority.ends_with(']') || !authority.contains(':') {
+        format!("{}:{}", authority, default_port)
+    } else {
+        authority.to_string()
+    };
+    resolve_public(&netloc)
+        .map(|_| ())
+        .map_err(|e| format!("Invalid webhook url: {}", e))
+}
+
+/// Resolve a `host:port`, refusing hosts with addresses which are not public.
+fn resolve_public(netloc: &str) -> io::Result<Vec<SocketAddr>> {
+    let addresses = netloc.to_socket_addrs()?.collect::<Vec<_>>();
+    if let Some(address) = addresses.iter().find(|address| !is_public(address.ip())) {
+        return Err(io::Error::new(
+            io::ErrorKind::PermissionDenied,
+            format!("{} is not a public address", address.ip()),
+        ));
+    }
+    Ok(addresses)
+}
+
+/// Whether an address is on the internet rather than loopback, link-local or private.
+fn is_public(ip: IpAddr) -> bool {
+    match ip {
+        IpAddr::V4(ip) => {
+            let [a, b, ..] = ip.octets();
+            !(ip.is_loopback()
+                || ip.is_private()
+                || ip.is_link_local()
+                || ip.is_unspecified()
+                || ip.is_broadcast()
+                || ip.is_multicast()
+                || ip.is_documentation()
+                // shared address space of carrier-grade NAT
+                || (a == 100 && b & 0xc0 == 64)
+                || a == 0)
+        }
+        IpAddr::V6(ip) => match ip.to_ipv4_mapped() {
+            Some(ip) => is_public(IpAddr::V4(ip)),
+            None => {
+                let first = ip.segments()[0];
+                !(ip.is_loopback()
+                    || ip.is_unspecified()
+                    || ip.is_multicast()
+                    // unique local and link-local
+                    || first & 0xfe00 == 0xfc00
+                    || first & 0xffc0 == 0xfe80)
+            }
+        },
+    }
+}
+
+/// The hex encoded HMAC-SHA256 of the payload.
+pub fn sign(secret: &str, payload: &str) -> String {
+    let mut engine = hmac::HmacEngine::<sha256::Hash>::new(secret.as_bytes());
+    engine.input(payload.as_bytes());
+    hmac::Hmac::<sha256::Hash>::from_engine(engine).to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::{
+        io::{BufRead, BufReader, Read, Write},
+        net::TcpListener,
+        sync::mpsc,
+        time::Instant,
+    };
+
+    /// A local webhook receiver answering with the given status codes, one per request.
+    fn receiver(statuses: Vec<u16>) -> (String, mpsc::Receiver<(Vec<String>, String)>) {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let url = format!("http://{}/hook", listener.local_addr().unwrap());
+        let (sender, requests) = mpsc::channel();
+        thread::spawn(move || {
+            for status in statuses {
+                let (mut stream, _) = listener.accept().unwrap();
+                let mut reader = BufReader::new(stream.try_clone().unwrap());
+                let mut headers = Vec::new();
+                loop {
+                    let mut line = String::new();
+                    reader.read_line(&mut line).unwrap();
+                    if line.trim().is_empty() {
+                        break;
+                    }
+                    headers.push(line.trim().to_lowercase());
+                }
+                let length = headers
+                    .iter()
+                    .find_map(|header| header.strip_prefix("content-length: "))
+                    .and_then(|length| length.parse().ok())
+                    .unwrap_or(0);
+                let mut body = vec![0; length];
+                reader.read_exact(&mut body).unwrap();
+                write!(
+                    stream,
+                    "HTTP/1.1 {} Status\r\nContent-Length: 0\r\nConnection: close\r\n\r\n",
+                    status
+                )
+                .unwrap();
+                sender
+                    .send((headers, String::from_utf8(body).unwrap()))
+                    .unwrap();
+            }
+        });
+        (url, requests)
+    }
+
+    #[test]
+    fn test_delivery_with_retry() {
+        let storage = Arc::new(Storage::open(None).unwrap());
+        let (url, requests) = receiver(vec![500, 200]);
+        let webhook_id = storage
+            .insert_webhook(&url, "secret", &["verification_completed".to_string()])
+            .unwrap();
+        // the receiver is local, which only tests may reach
+        let agent = ureq::AgentBuilder::new().build();
+        let webhooks = Webhooks::start(storage.clone(), agent, Duration::from_millis(10));
+
+        webhooks.notify("verification_failed", json!({ "error": "ignored" }));
+        webhooks.notify("verification_completed", json!({ "spendable": 10 }));
+
+        for _ in 0..2 {
+            let (headers, body) = requests.recv_timeout(Duration::from_secs(10)).unwrap();
+            let payload: serde_json::Value = serde_json::from_str(&body).unwrap();
+            assert_eq!(payload["event"], "verification_completed");
+            assert_eq!(payload["data"]["spendable"], 10);
+            assert!(headers.contains(&format!(
+                "x-webhook-signature: sha256={}",
+                sign("secret", &body)
+            )));
+        }
+
+        let start = Instant::now();
+        let delivery = loop {
+            let mut deliveries = storage.list_webhook_deliveries(webhook_id).unwrap();
+            assert_eq!(deliveries.len(), 1);
+            let delivery = deliveries.remove(0);
+            if delivery.status != "pending" || start.elapsed() > Duration::from_secs(10) {
+                break delivery;
+            }
+            thread::sleep(Duration::from_millis(10));
+        };
+        assert_eq!(delivery.status, "delivered");
+        assert_eq!(delivery.attempts, 2);
+        assert_eq!(delivery.response_status, Some(200));
+    }
+
+    #[test]
+    fn test_check_url() {
+        assert!(check_url("http://8.8.8.8/hook").is_ok());
+        assert!(check_url("https://user@[2001:4860:4860::8888]:8443/hook?a=b").is_ok());
+        assert!(check_url("ftp://8.8.8.8/hook").is_err());
+        for url in [
+            "http://127.0.0.1:8080/hook",
+            "http://localhost/hook",
+            "http://10.0.0.1",
+            "http://192.168.1.1/hook",
+            "http://169.254.169.254/latest/meta-data",
+            "http://user@127.0.0.1#@8.8.8.8/",
+            "http://[::1]/hook",
+            "http://[fd00::1]:80/hook",
+            "http://[::ffff:127.0.0.1]/hook",
+        ] {
+            assert!(check_url(url).is_err(), "{}", url);
+        }
+        // delivered through the same check
+        assert!(resolve_public("127.0.0.1:80").is_err());
+    }
+
+    #[test]
+    fn test_sign() {
+        // RFC 4231 test case 2
+        assert_eq!(
+            sign("Jefe", "what do ya want for nothing?"),
+            "5bdcc146bf60754e6a042426089575c75a003f089d2739839dec58b964ec3843"
+        );
+    }
+}